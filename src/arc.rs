@@ -0,0 +1,154 @@
+use std::f32::consts::PI;
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::line::LineMode;
+use crate::line_style::LineStyle;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::stroke::Stroke;
+
+// Maximal distance in pixels between the arc and the segments approximating it
+const FLATTENING_TOLERANCE: f32 = 0.25;
+// Arcs are approximated by at most this many segments, arcs so large that they need more are far beyond any canvas
+const MAX_SEGMENTS: usize = 1 << 16;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ArcKind {
+    Open,
+    Chord,
+    Pie
+}
+
+#[derive(Copy, Clone)]
+pub struct Arc {
+    pub center: Point2d,
+    pub radius_x: f32,
+    pub radius_y: f32,
+    pub rotation: f32,
+    // Angles in radians, counter clockwise starting from the radius_x axis
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+    pub kind: ArcKind,
+    pub filled: bool,
+    pub color: PixelColor,
+    pub mode: LineMode,
+    pub style: LineStyle,
+    pub compositing: Compositing,
+    pub stroke: Option<Stroke>
+}
+
+impl Arc {
+    pub fn new(color: PixelColor, center: Point2d, radius_x: f32, radius_y: f32, start_angle: f32, sweep_angle: f32) -> Arc {
+        Arc {
+            center,
+            radius_x,
+            radius_y,
+            rotation: 0.0,
+            start_angle,
+            sweep_angle,
+            kind: ArcKind::Open,
+            filled: false,
+            color,
+            mode: LineMode::Aliased,
+            style: LineStyle::Solid,
+            compositing: Compositing::source_over(),
+            stroke: None
+        }
+    }
+
+    pub fn circular(color: PixelColor, center: Point2d, radius: f32, start_angle: f32, sweep_angle: f32) -> Arc {
+        Arc::new(color, center, radius, radius, start_angle, sweep_angle)
+    }
+
+    pub fn with_kind(&self, kind: ArcKind) -> Arc {
+        Arc { kind, ..*self }
+    }
+
+    pub fn with_fill(&self, filled: bool) -> Arc {
+        Arc { filled, ..*self }
+    }
+
+    pub fn with_rotation(&self, rotation: f32) -> Arc {
+        Arc { rotation, ..*self }
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> Arc {
+        Arc { mode, ..*self }
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> Arc {
+        Arc { style, ..*self }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Arc {
+        Arc { compositing, ..*self }
+    }
+
+    pub fn with_stroke(&self, stroke: Stroke) -> Arc {
+        Arc { stroke: Some(stroke), ..*self }
+    }
+
+    pub fn point_at(&self, angle: f32) -> Point2d {
+        let (sin, cos) = angle.sin_cos();
+        let (rotation_sin, rotation_cos) = self.rotation.sin_cos();
+        let x = self.radius_x * cos;
+        let y = self.radius_y * sin;
+        Point2d::new(self.center.x + x * rotation_cos - y * rotation_sin,
+                     self.center.y + x * rotation_sin + y * rotation_cos)
+    }
+
+    // Points of the arc itself, segment count depends on the radius so the error stays under the tolerance.
+    // The step is computed in f64, in f32 it vanishes for radii above a few millions.
+    pub fn points(&self) -> Vec<Point2d> {
+        let sweep = self.sweep_angle.clamp(-2.0 * PI, 2.0 * PI);
+        let radius = self.radius_x.abs().max(self.radius_y.abs()) as f64;
+        let tolerance = FLATTENING_TOLERANCE as f64;
+        let step = if radius > tolerance {
+            2.0 * (1.0 - tolerance / radius).acos()
+        } else {
+            std::f64::consts::FRAC_PI_2
+        };
+        let count = ((sweep.abs() as f64 / step).ceil() as usize).clamp(1, MAX_SEGMENTS);
+        (0..(count + 1))
+            .map(|i| self.point_at(self.start_angle + sweep * i as f32 / count as f32))
+            .collect()
+    }
+
+    // Outline of the shape described by kind, closed for chords and pies
+    pub fn outline(&self) -> Polyline {
+        let mut points = self.points();
+        if self.kind == ArcKind::Pie {
+            points.push(self.center);
+        }
+        let polyline = if self.kind == ArcKind::Open {
+            Polyline::new(self.color, points)
+        } else {
+            Polyline::closed(self.color, points)
+        };
+        let polyline = polyline.with_style(self.style).with_mode(self.mode).with_compositing(self.compositing);
+        match self.stroke {
+            Some(stroke) => polyline.with_stroke(stroke),
+            None => polyline
+        }
+    }
+}
+
+impl Draw for Arc {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let valid = [self.center.x, self.center.y, self.radius_x, self.radius_y, self.rotation, self.start_angle, self.sweep_angle]
+            .iter()
+            .all(|value| value.is_finite());
+        if !valid {
+            return;
+        }
+        if self.filled && self.kind != ArcKind::Open {
+            let outline = self.outline();
+            Polygon::new(self.color, outline.points).with_compositing(self.compositing).draw(canvas);
+        } else {
+            self.outline().draw(canvas);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::f32::consts::PI;
+use crate::canvas::Canvas;
+use crate::arc::{Arc, ArcKind};
+use crate::draw::Draw;
+use crate::line::LineMode;
+use crate::line_style::LineStyle;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::path::Path;
+use crate::stroke::{LineCap, Stroke};
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden};
+
+#[test]
+fn arcs_chords_and_pies() {
+    assert_golden("arcs_chords_and_pies", vec![
+        Box::new(Arc::circular(PixelColor::red(), Point2d::new(16.0, 16.0), 12.0, 0.0, 1.5 * PI)),
+        Box::new(Arc::circular(PixelColor::rgb(0, 255, 0), Point2d::new(48.0, 16.0), 12.0, 0.25 * PI, PI)
+            .with_kind(ArcKind::Chord)),
+        Box::new(Arc::circular(PixelColor::rgb(0, 0, 255), Point2d::new(16.0, 48.0), 12.0, -0.25 * PI, -1.25 * PI)
+            .with_kind(ArcKind::Pie)
+            .with_fill(true)),
+        Box::new(Arc::new(PixelColor::rgb(255, 255, 0), Point2d::new(48.0, 48.0), 13.0, 7.0, 0.0, PI)
+            .with_rotation(0.4)
+            .with_kind(ArcKind::Pie)
+            .with_style(LineStyle::dashed(&[3.0, 2.0], 0.0).unwrap())),
+        Box::new(Arc::circular(PixelColor::rgb(255, 0, 255), Point2d::new(48.0, 48.0), 15.0, PI, 0.75 * PI)
+            .with_mode(LineMode::AntiAliased)),
+        Box::new(Arc::circular(PixelColor::rgb(0, 255, 255), Point2d::new(16.0, 16.0), 6.0, 0.5 * PI, PI)
+            .with_stroke(Stroke::new(3.0).with_cap(LineCap::Round)))
+    ]);
+}
+
+#[test]
+fn arcs_with_huge_radii() {
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    for radius in [1e7f32, 1e8, 1e20, f32::MAX].iter() {
+        let arc = Arc::circular(PixelColor::red(), Point2d::new(32.0, 32.0), *radius, 0.0, 1.0);
+        assert!(arc.points().len() > 1 && arc.points().len() <= (1 << 16) + 1, "radius {}", radius);
+        arc.draw(&mut canvas);
+    }
+    // A nearly straight arc across the canvas
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Path::new(PixelColor::red()).move_to(Point2d::new(0.0, 32.0)).arc_to(1e8, 1e8, 0.0, false, true, Point2d::new(63.0, 32.0))
+        .draw(&mut canvas);
+    for x in 0..WIDTH as i32 {
+        assert!((28..37).any(|y| canvas.get_pixel(x, y) == Some(PixelColor::red())), "column {}", x);
+    }
+}
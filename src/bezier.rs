@@ -0,0 +1,256 @@
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::line::LineMode;
+use crate::line_style::LineStyle;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::stroke::Stroke;
+
+pub const DEFAULT_TOLERANCE: f32 = 0.25;
+const MAX_SUBDIVISION_DEPTH: u32 = 16;
+
+fn midpoint(first: &Point2d, second: &Point2d) -> Point2d {
+    Point2d::new((first.x + second.x) / 2.0, (first.y + second.y) / 2.0)
+}
+
+// Appends points approximating the curve, without the start point, so consecutive curves can be chained
+pub fn flatten_quadratic(start: &Point2d, control: &Point2d, end: &Point2d, tolerance: f32, result: &mut Vec<Point2d>) {
+    flatten_quadratic_recursive(start, control, end, tolerance.max(1e-3), MAX_SUBDIVISION_DEPTH, result);
+}
+
+fn flatten_quadratic_recursive(start: &Point2d, control: &Point2d, end: &Point2d, tolerance: f32, depth: u32,
+                               result: &mut Vec<Point2d>) {
+    // Biggest distance between the curve and its chord is a quarter of |start - 2 control + end|
+    let deviation_x = start.x - 2.0 * control.x + end.x;
+    let deviation_y = start.y - 2.0 * control.y + end.y;
+    let deviation = (deviation_x * deviation_x + deviation_y * deviation_y).sqrt() / 4.0;
+    if depth == 0 || deviation <= tolerance || !deviation.is_finite() {
+        result.push(*end);
+    } else {
+        let first_control = midpoint(start, control);
+        let second_control = midpoint(control, end);
+        let middle = midpoint(&first_control, &second_control);
+        flatten_quadratic_recursive(start, &first_control, &middle, tolerance, depth - 1, result);
+        flatten_quadratic_recursive(&middle, &second_control, end, tolerance, depth - 1, result);
+    }
+}
+
+pub fn flatten_cubic(start: &Point2d, first_control: &Point2d, second_control: &Point2d, end: &Point2d, tolerance: f32,
+                     result: &mut Vec<Point2d>) {
+    let points = [*start, *first_control, *second_control, *end];
+    flatten_cubic_recursive(&points, tolerance.max(1e-3), MAX_SUBDIVISION_DEPTH, result);
+}
+
+fn flatten_cubic_recursive(points: &[Point2d; 4], tolerance: f32, depth: u32, result: &mut Vec<Point2d>) {
+    let [start, first_control, second_control, end] = points;
+    // Flatness bound by Roger Willcocks, the curve stays within tolerance of the chord when it holds
+    let ux = (3.0 * first_control.x - 2.0 * start.x - end.x).powi(2).max((3.0 * second_control.x - start.x - 2.0 * end.x).powi(2));
+    let uy = (3.0 * first_control.y - 2.0 * start.y - end.y).powi(2).max((3.0 * second_control.y - start.y - 2.0 * end.y).powi(2));
+    let flatness = ux + uy;
+    if depth == 0 || flatness <= 16.0 * tolerance * tolerance || !flatness.is_finite() {
+        result.push(*end);
+    } else {
+        let p01 = midpoint(start, first_control);
+        let p12 = midpoint(first_control, second_control);
+        let p23 = midpoint(second_control, end);
+        let p012 = midpoint(&p01, &p12);
+        let p123 = midpoint(&p12, &p23);
+        let middle = midpoint(&p012, &p123);
+        flatten_cubic_recursive(&[*start, p01, p012, middle], tolerance, depth - 1, result);
+        flatten_cubic_recursive(&[middle, p123, p23, *end], tolerance, depth - 1, result);
+    }
+}
+
+// Drawing options shared by both curve kinds
+#[derive(Copy, Clone)]
+pub struct CurveStyle {
+    pub color: PixelColor,
+    pub tolerance: f32,
+    pub filled: bool,
+    pub mode: LineMode,
+    pub style: LineStyle,
+    pub compositing: Compositing,
+    pub stroke: Option<Stroke>
+}
+
+impl CurveStyle {
+    pub fn new(color: PixelColor) -> CurveStyle {
+        CurveStyle {
+            color,
+            tolerance: DEFAULT_TOLERANCE,
+            filled: false,
+            mode: LineMode::Aliased,
+            style: LineStyle::Solid,
+            compositing: Compositing::source_over(),
+            stroke: None
+        }
+    }
+
+    pub fn with_tolerance(&self, tolerance: f32) -> CurveStyle {
+        CurveStyle { tolerance, ..*self }
+    }
+
+    pub fn with_fill(&self, filled: bool) -> CurveStyle {
+        CurveStyle { filled, ..*self }
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> CurveStyle {
+        CurveStyle { mode, ..*self }
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> CurveStyle {
+        CurveStyle { style, ..*self }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> CurveStyle {
+        CurveStyle { compositing, ..*self }
+    }
+
+    pub fn with_stroke(&self, stroke: Stroke) -> CurveStyle {
+        CurveStyle { stroke: Some(stroke), ..*self }
+    }
+
+    // Filled curves are closed with a straight segment from the end back to the start
+    fn draw_points(&self, points: Vec<Point2d>, canvas: &mut dyn Canvas) {
+        if !points.iter().all(|p| p.x.is_finite() && p.y.is_finite()) {
+            return;
+        }
+        if self.filled {
+            Polygon::new(self.color, points).with_compositing(self.compositing).draw(canvas);
+        } else {
+            let polyline = Polyline::new(self.color, points)
+                .with_mode(self.mode)
+                .with_style(self.style)
+                .with_compositing(self.compositing);
+            match self.stroke {
+                Some(stroke) => polyline.with_stroke(stroke).draw(canvas),
+                None => polyline.draw(canvas)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct QuadraticBezier {
+    pub start: Point2d,
+    pub control: Point2d,
+    pub end: Point2d,
+    pub curve_style: CurveStyle
+}
+
+impl QuadraticBezier {
+    pub fn new(color: PixelColor, start: Point2d, control: Point2d, end: Point2d) -> QuadraticBezier {
+        QuadraticBezier { start, control, end, curve_style: CurveStyle::new(color) }
+    }
+
+    pub fn with_curve_style(&self, curve_style: CurveStyle) -> QuadraticBezier {
+        QuadraticBezier { curve_style, ..*self }
+    }
+
+    pub fn with_tolerance(&self, tolerance: f32) -> QuadraticBezier {
+        self.with_curve_style(self.curve_style.with_tolerance(tolerance))
+    }
+
+    pub fn with_fill(&self, filled: bool) -> QuadraticBezier {
+        self.with_curve_style(self.curve_style.with_fill(filled))
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> QuadraticBezier {
+        self.with_curve_style(self.curve_style.with_mode(mode))
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> QuadraticBezier {
+        self.with_curve_style(self.curve_style.with_style(style))
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> QuadraticBezier {
+        self.with_curve_style(self.curve_style.with_compositing(compositing))
+    }
+
+    pub fn with_stroke(&self, stroke: Stroke) -> QuadraticBezier {
+        self.with_curve_style(self.curve_style.with_stroke(stroke))
+    }
+
+    pub fn point_at(&self, t: f32) -> Point2d {
+        let s = 1.0 - t;
+        Point2d::new(s * s * self.start.x + 2.0 * s * t * self.control.x + t * t * self.end.x,
+                     s * s * self.start.y + 2.0 * s * t * self.control.y + t * t * self.end.y)
+    }
+
+    pub fn flatten(&self, tolerance: f32) -> Vec<Point2d> {
+        let mut result = vec![self.start];
+        flatten_quadratic(&self.start, &self.control, &self.end, tolerance, &mut result);
+        result
+    }
+}
+
+impl Draw for QuadraticBezier {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        self.curve_style.draw_points(self.flatten(self.curve_style.tolerance), canvas);
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct CubicBezier {
+    pub start: Point2d,
+    pub first_control: Point2d,
+    pub second_control: Point2d,
+    pub end: Point2d,
+    pub curve_style: CurveStyle
+}
+
+impl CubicBezier {
+    pub fn new(color: PixelColor, start: Point2d, first_control: Point2d, second_control: Point2d, end: Point2d) -> CubicBezier {
+        CubicBezier { start, first_control, second_control, end, curve_style: CurveStyle::new(color) }
+    }
+
+    pub fn with_curve_style(&self, curve_style: CurveStyle) -> CubicBezier {
+        CubicBezier { curve_style, ..*self }
+    }
+
+    pub fn with_tolerance(&self, tolerance: f32) -> CubicBezier {
+        self.with_curve_style(self.curve_style.with_tolerance(tolerance))
+    }
+
+    pub fn with_fill(&self, filled: bool) -> CubicBezier {
+        self.with_curve_style(self.curve_style.with_fill(filled))
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> CubicBezier {
+        self.with_curve_style(self.curve_style.with_mode(mode))
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> CubicBezier {
+        self.with_curve_style(self.curve_style.with_style(style))
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> CubicBezier {
+        self.with_curve_style(self.curve_style.with_compositing(compositing))
+    }
+
+    pub fn with_stroke(&self, stroke: Stroke) -> CubicBezier {
+        self.with_curve_style(self.curve_style.with_stroke(stroke))
+    }
+
+    pub fn point_at(&self, t: f32) -> Point2d {
+        let s = 1.0 - t;
+        let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+        Point2d::new(a * self.start.x + b * self.first_control.x + c * self.second_control.x + d * self.end.x,
+                     a * self.start.y + b * self.first_control.y + c * self.second_control.y + d * self.end.y)
+    }
+
+    pub fn flatten(&self, tolerance: f32) -> Vec<Point2d> {
+        let mut result = vec![self.start];
+        flatten_cubic(&self.start, &self.first_control, &self.second_control, &self.end, tolerance, &mut result);
+        result
+    }
+}
+
+impl Draw for CubicBezier {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        self.curve_style.draw_points(self.flatten(self.curve_style.tolerance), canvas);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::bezier::{CubicBezier, QuadraticBezier};
+use crate::line::LineMode;
+use crate::line_style::LineStyle;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::stroke::{LineJoin, Stroke};
+use crate::golden_tests::assert_golden;
+
+#[test]
+fn bezier_curves() {
+    assert_golden("bezier_curves", vec![
+        Box::new(QuadraticBezier::new(PixelColor::red(), Point2d::new(2.0, 4.0), Point2d::new(16.0, 60.0),
+                                      Point2d::new(30.0, 4.0))),
+        Box::new(QuadraticBezier::new(PixelColor::rgb(0, 255, 0), Point2d::new(6.0, 4.0), Point2d::new(16.0, 40.0),
+                                      Point2d::new(26.0, 4.0))
+            .with_fill(true)),
+        Box::new(CubicBezier::new(PixelColor::rgb(0, 0, 255), Point2d::new(34.0, 4.0), Point2d::new(34.0, 60.0),
+                                  Point2d::new(62.0, 4.0), Point2d::new(62.0, 60.0))
+            .with_mode(LineMode::AntiAliased)),
+        Box::new(CubicBezier::new(PixelColor::rgb(255, 255, 0), Point2d::new(4.0, 50.0), Point2d::new(20.0, 70.0),
+                                  Point2d::new(40.0, 30.0), Point2d::new(60.0, 56.0))
+            .with_tolerance(2.0)
+            .with_style(LineStyle::dashed(&[4.0, 2.0], 0.0).unwrap())),
+        Box::new(CubicBezier::new(PixelColor::rgb(0, 255, 255), Point2d::new(4.0, 40.0), Point2d::new(20.0, 60.0),
+                                  Point2d::new(40.0, 20.0), Point2d::new(60.0, 46.0))
+            .with_stroke(Stroke::new(3.0).with_join(LineJoin::Round)))
+    ]);
+}
@@ -0,0 +1,157 @@
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::pattern::Sampling;
+use crate::pixel_color::PixelColor;
+
+// Rectangle of pixels with the lower left corner at (x, y)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Region {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32
+}
+
+impl Region {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Region {
+        Region { x, y, width, height }
+    }
+
+    pub fn of_canvas(canvas: &dyn Canvas) -> Region {
+        Region::new(0, 0, canvas.width(), canvas.height())
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width as i32 && y < self.y + self.height as i32
+    }
+
+    pub fn intersection(&self, other: &Region) -> Option<Region> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let top = (self.y + self.height as i32).min(other.y + other.height as i32);
+        if right <= x || top <= y {
+            return None;
+        }
+        Some(Region::new(x, y, (right - x) as u32, (top - y) as u32))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct BlitOptions {
+    // Source pixels with this red, green and blue are not copied
+    pub color_key: Option<PixelColor>,
+    // Opacity applied on top of the source alpha
+    pub alpha: u8,
+    pub sampling: Sampling,
+    pub compositing: Compositing
+}
+
+impl BlitOptions {
+    pub fn new() -> BlitOptions {
+        BlitOptions { color_key: None, alpha: 255, sampling: Sampling::Nearest, compositing: Compositing::source_over() }
+    }
+
+    pub fn with_color_key(&self, color_key: PixelColor) -> BlitOptions {
+        BlitOptions { color_key: Some(color_key), ..*self }
+    }
+
+    pub fn with_alpha(&self, alpha: u8) -> BlitOptions {
+        BlitOptions { alpha, ..*self }
+    }
+
+    pub fn with_sampling(&self, sampling: Sampling) -> BlitOptions {
+        BlitOptions { sampling, ..*self }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> BlitOptions {
+        BlitOptions { compositing, ..*self }
+    }
+
+    fn is_keyed(&self, color: &PixelColor) -> bool {
+        match self.color_key {
+            Some(key) => key.red == color.red && key.green == color.green && key.blue == color.blue,
+            None => false
+        }
+    }
+}
+
+impl Default for BlitOptions {
+    fn default() -> BlitOptions {
+        BlitOptions::new()
+    }
+}
+
+// Copies the source region so that its lower left corner lands on (x, y)
+pub fn blit(source: &dyn Canvas, source_region: &Region, destination: &mut dyn Canvas, x: i32, y: i32,
+            options: &BlitOptions) {
+    let destination_region = Region::new(x, y, source_region.width, source_region.height);
+    blit_scaled(source, source_region, destination, &destination_region, options);
+}
+
+// Stretches the source region over the destination region, destination pixel centers are mapped into the source
+pub fn blit_scaled(source: &dyn Canvas, source_region: &Region, destination: &mut dyn Canvas,
+                   destination_region: &Region, options: &BlitOptions) {
+    let readable = match source_region.intersection(&Region::of_canvas(source)) {
+        Some(readable) => readable,
+        None => return
+    };
+    let visible = match destination_region.intersection(&Region::of_canvas(destination)) {
+        Some(visible) => visible,
+        None => return
+    };
+    let scale_x = source_region.width as f32 / destination_region.width as f32;
+    let scale_y = source_region.height as f32 / destination_region.height as f32;
+    let coverage = options.alpha as f32 / 255.0;
+    for y in visible.y..(visible.y + visible.height as i32) {
+        let source_y = source_region.y as f32 + (y - destination_region.y) as f32 * scale_y + 0.5 * scale_y;
+        for x in visible.x..(visible.x + visible.width as i32) {
+            let source_x = source_region.x as f32 + (x - destination_region.x) as f32 * scale_x + 0.5 * scale_x;
+            let color = match options.sampling {
+                Sampling::Nearest => sample_nearest(source, &readable, source_x, source_y, options),
+                Sampling::Bilinear => sample_bilinear(source, &readable, source_x, source_y, options)
+            };
+            if let Some(color) = color {
+                destination.composite_pixel(x, y, &color, coverage, &options.compositing);
+            }
+        }
+    }
+}
+
+fn sample_nearest(source: &dyn Canvas, readable: &Region, x: f32, y: f32, options: &BlitOptions) -> Option<PixelColor> {
+    let (x, y) = (x.floor() as i32, y.floor() as i32);
+    if !readable.contains(x, y) {
+        return None;
+    }
+    let color = source.get_pixel(x, y)?;
+    if options.is_keyed(&color) { None } else { Some(color) }
+}
+
+// Colors are interpolated premultiplied by alpha, so keyed pixels do not bleed into their neighbours
+fn sample_bilinear(source: &dyn Canvas, readable: &Region, x: f32, y: f32, options: &BlitOptions) -> Option<PixelColor> {
+    let (u, v) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (u.floor() as i32, v.floor() as i32);
+    let (ratio_x, ratio_y) = (u - u.floor(), v - v.floor());
+    let last_x = readable.x + readable.width as i32 - 1;
+    let last_y = readable.y + readable.height as i32 - 1;
+    let mut sum = [0.0f32; 4];
+    for (dx, dy, weight) in [(0, 0, (1.0 - ratio_x) * (1.0 - ratio_y)), (1, 0, ratio_x * (1.0 - ratio_y)),
+                             (0, 1, (1.0 - ratio_x) * ratio_y), (1, 1, ratio_x * ratio_y)].iter() {
+        let sample_x = (x0 + dx).clamp(readable.x, last_x);
+        let sample_y = (y0 + dy).clamp(readable.y, last_y);
+        let color = source.get_pixel(sample_x, sample_y)?;
+        if options.is_keyed(&color) {
+            continue;
+        }
+        let alpha = color.alpha as f32 * weight;
+        sum[0] += color.red as f32 * alpha;
+        sum[1] += color.green as f32 * alpha;
+        sum[2] += color.blue as f32 * alpha;
+        sum[3] += alpha;
+    }
+    if sum[3] < 0.5 {
+        return None;
+    }
+    let channel = |value: f32| (value / sum[3]).round().clamp(0.0, 255.0) as u8;
+    Some(PixelColor::new(channel(sum[0]), channel(sum[1]), channel(sum[2]), sum[3].round().min(255.0) as u8))
+}
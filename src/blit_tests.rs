@@ -0,0 +1,36 @@
+use crate::canvas::Canvas;
+use crate::blit::{BlitOptions, Region, blit, blit_scaled};
+use crate::circle::Circle;
+use crate::draw::Draw;
+use crate::line::Line;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::pattern::Sampling;
+use crate::polygon::Polygon;
+use crate::golden_tests::{assert_golden_canvas, render};
+
+#[test]
+fn blits() {
+    let key = PixelColor::rgb(255, 0, 255);
+    let mut sprite = MemoryCanvas::new(12, 12);
+    sprite.clean_color(&key);
+    Circle::filled(PixelColor::rgb(255, 255, 0), Point2d::new(6.0, 6.0), 5.0).draw(&mut sprite);
+    Line::with_color(PixelColor::rgb(0, 0, 255), 0.0, 0.0, 11.0, 11.0).draw(&mut sprite);
+    let mut canvas = render(&[Box::new(Polygon::new(PixelColor::rgb(0, 128, 0), vec![
+        Point2d::new(0.0, 32.0), Point2d::new(64.0, 32.0), Point2d::new(64.0, 64.0), Point2d::new(0.0, 64.0)]))]);
+    let whole = Region::of_canvas(&sprite);
+    blit(&sprite, &whole, &mut canvas, 2, 2, &BlitOptions::new());
+    blit(&sprite, &whole, &mut canvas, 18, 2, &BlitOptions::new().with_color_key(key));
+    blit(&sprite, &whole, &mut canvas, 34, 2, &BlitOptions::new().with_color_key(key).with_alpha(128));
+    blit(&sprite, &Region::new(6, 6, 8, 8), &mut canvas, 50, 2, &BlitOptions::new().with_color_key(key));
+    blit(&sprite, &whole, &mut canvas, 58, 20, &BlitOptions::new().with_color_key(key));
+    blit_scaled(&sprite, &whole, &mut canvas, &Region::new(2, 36, 24, 24), &BlitOptions::new().with_color_key(key));
+    blit_scaled(&sprite, &whole, &mut canvas, &Region::new(30, 36, 32, 16),
+                &BlitOptions::new().with_color_key(key).with_sampling(Sampling::Bilinear));
+    assert_golden_canvas("blits", &canvas);
+    // Unscaled blit copies pixels exactly
+    let mut copy = MemoryCanvas::new(12, 12);
+    blit(&sprite, &whole, &mut copy, 0, 0, &BlitOptions::new().with_sampling(Sampling::Bilinear));
+    assert_eq!(copy.pixels(), sprite.pixels());
+}
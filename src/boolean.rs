@@ -0,0 +1,464 @@
+use std::collections::{HashMap, HashSet};
+use crate::point2d::Point2d;
+use crate::polygon::{FillRule, Polygon};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BooleanOperation {
+    Union,
+    Intersection,
+    Difference,
+    Xor
+}
+
+type Vertex = (f64, f64);
+
+// Points closer than this, relative to the magnitude of the coordinates, are the same point, and a point closer
+// to an edge lies on it
+const TOUCH_EPSILON: f64 = 1e-9;
+
+fn to_vertices(points: &[Point2d]) -> Vec<Vertex> {
+    let mut result: Vec<Vertex> = Vec::with_capacity(points.len());
+    for point in points.iter() {
+        let vertex = (point.x as f64, point.y as f64);
+        if result.last() != Some(&vertex) {
+            result.push(vertex);
+        }
+    }
+    if result.len() > 1 && result.first() == result.last() {
+        result.pop();
+    }
+    result
+}
+
+fn to_points(ring: &[Vertex]) -> Vec<Point2d> {
+    ring.iter().map(|(x, y)| Point2d::new(*x as f32, *y as f32)).collect()
+}
+
+fn signed_area(ring: &[Vertex]) -> f64 {
+    let length = ring.len();
+    (0..length).map(|i| {
+        let (a, b) = (ring[i], ring[(i + 1) % length]);
+        a.0 * b.1 - b.0 * a.1
+    }).sum::<f64>() / 2.0
+}
+
+fn oriented(ring: Vec<Vertex>, counter_clockwise: bool) -> Vec<Vertex> {
+    if (signed_area(&ring) > 0.0) == counter_clockwise {
+        ring
+    } else {
+        ring.into_iter().rev().collect()
+    }
+}
+
+// Winding of the ring around the point, the point must not lie on it
+fn winding_number(point: Vertex, ring: &[Vertex]) -> i32 {
+    let length = ring.len();
+    let mut winding = 0;
+    for i in 0..length {
+        let (a, b) = (ring[i], ring[(i + 1) % length]);
+        let side = (b.0 - a.0) * (point.1 - a.1) - (point.0 - a.0) * (b.1 - a.1);
+        if a.1 <= point.1 && b.1 > point.1 && side > 0.0 {
+            winding += 1;
+        } else if b.1 <= point.1 && a.1 > point.1 && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+// Area covered by a polygon according to its fill rule, the same as it is drawn. Rings may be nested
+// but must not cross each other, parts of them without area are removed.
+struct Region {
+    rings: Vec<Vec<Vertex>>,
+    fill_rule: FillRule,
+    complemented: bool
+}
+
+impl Region {
+    fn new(polygon: &Polygon) -> Region {
+        let rings = std::iter::once(&polygon.points).chain(polygon.holes.iter())
+            .map(|ring| simplified(to_vertices(ring), 0.0))
+            .filter(|ring| ring.len() >= 3)
+            .collect();
+        Region { rings, fill_rule: polygon.fill_rule, complemented: false }
+    }
+
+    fn complement(&self) -> Region {
+        Region { complemented: !self.complemented, rings: self.rings.clone(), ..*self }
+    }
+
+    fn is_inside(&self, winding: i32) -> bool {
+        self.fill_rule.is_inside(winding) != self.complemented
+    }
+
+    // The point must not lie on any of the rings
+    fn contains(&self, point: Vertex) -> bool {
+        self.is_inside(self.rings.iter().map(|ring| winding_number(point, ring)).sum())
+    }
+
+    // Rings separating the inside from the outside, oriented with the inside on the left of every edge.
+    // Winding changes by one across a ring, which need not change the side for the non-zero rule.
+    fn boundary(&self) -> Vec<Vec<Vertex>> {
+        self.rings.iter().enumerate().filter_map(|(index, ring)| {
+            let outside: i32 = self.rings.iter().enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, other)| winding_number(ring[0], other))
+                .sum();
+            let inside = outside + if signed_area(ring) > 0.0 { 1 } else { -1 };
+            if self.is_inside(inside) == self.is_inside(outside) {
+                None
+            } else {
+                Some(oriented(ring.clone(), self.is_inside(inside)))
+            }
+        }).collect()
+    }
+}
+
+fn distance_to_segment(point: Vertex, a: Vertex, b: Vertex) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 { (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length).clamp(0.0, 1.0) } else { 0.0 };
+    ((a.0 + t * dx - point.0).powi(2) + (a.1 + t * dy - point.1).powi(2)).sqrt()
+}
+
+// Where an edge piece lies relative to the other region
+#[derive(Copy, Clone, PartialEq)]
+enum Side {
+    Inside,
+    Outside,
+    // Both regions have the same edge, with their interiors on the same side or on opposite sides of it
+    SharedSame,
+    SharedOpposite
+}
+
+// Piece of a cut edge between two point indices
+#[derive(Copy, Clone)]
+struct Piece {
+    from: usize,
+    to: usize,
+    side: Side
+}
+
+fn edges(rings: &[Vec<Vertex>]) -> Vec<(Vertex, Vertex)> {
+    rings.iter().flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()]))).collect()
+}
+
+// Points of both outlines, a point within the tolerance of an earlier one is merged into it. Points are
+// looked up in a grid with cells of the tolerance size, merged points lie in neighbouring cells.
+struct PointIndex {
+    points: Vec<Vertex>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    tolerance: f64
+}
+
+impl PointIndex {
+    fn new(tolerance: f64) -> PointIndex {
+        PointIndex { points: Vec::new(), cells: HashMap::new(), tolerance }
+    }
+
+    fn index(&mut self, point: Vertex) -> usize {
+        let cell = ((point.0 / self.tolerance).floor() as i64, (point.1 / self.tolerance).floor() as i64);
+        let mut merged = None;
+        for x in (cell.0 - 1)..(cell.0 + 2) {
+            for y in (cell.1 - 1)..(cell.1 + 2) {
+                for index in self.cells.get(&(x, y)).iter().flat_map(|indices| indices.iter()) {
+                    let other = self.points[*index];
+                    if (other.0 - point.0).hypot(other.1 - point.1) < self.tolerance {
+                        merged = Some(merged.map_or(*index, |merged: usize| merged.min(*index)));
+                    }
+                }
+            }
+        }
+        merged.unwrap_or_else(|| {
+            self.points.push(point);
+            self.cells.entry(cell).or_default().push(self.points.len() - 1);
+            self.points.len() - 1
+        })
+    }
+}
+
+// Pairs of subject and clip edges whose bounding boxes, grown by the tolerance, overlap. Edges are swept
+// along x, every edge is compared with the edges of the other outline whose x range is still open.
+fn candidate_pairs(subject_edges: &[(Vertex, Vertex)], clip_edges: &[(Vertex, Vertex)], tolerance: f64) -> Vec<(usize, usize)> {
+    let bounds = |(a, b): &(Vertex, Vertex)| (a.0.min(b.0) - tolerance, a.0.max(b.0) + tolerance,
+                                               a.1.min(b.1) - tolerance, a.1.max(b.1) + tolerance);
+    let (subject_bounds, clip_bounds): (Vec<_>, Vec<_>) = (subject_edges.iter().map(bounds).collect(), clip_edges.iter().map(bounds).collect());
+    let mut events: Vec<(f64, bool, usize)> = subject_bounds.iter().enumerate().map(|(index, bounds)| (bounds.0, true, index))
+        .chain(clip_bounds.iter().enumerate().map(|(index, bounds)| (bounds.0, false, index)))
+        .collect();
+    events.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (mut open_subject, mut open_clip): (Vec<usize>, Vec<usize>) = (Vec::new(), Vec::new());
+    let mut pairs = Vec::new();
+    for (x, is_subject, index) in events.into_iter() {
+        open_subject.retain(|open| subject_bounds[*open].1 >= x);
+        open_clip.retain(|open| clip_bounds[*open].1 >= x);
+        let overlap = |a: &(f64, f64, f64, f64), b: &(f64, f64, f64, f64)| a.2 <= b.3 && b.2 <= a.3;
+        if is_subject {
+            pairs.extend(open_clip.iter().filter(|open| overlap(&subject_bounds[index], &clip_bounds[**open])).map(|open| (index, *open)));
+            open_subject.push(index);
+        } else {
+            pairs.extend(open_subject.iter().filter(|open| overlap(&subject_bounds[**open], &clip_bounds[index])).map(|open| (*open, index)));
+            open_clip.push(index);
+        }
+    }
+    pairs
+}
+
+// Point where the segments cross each other, excluding their end points
+fn crossing(a: Vertex, b: Vertex, c: Vertex, d: Vertex) -> Option<Vertex> {
+    let (first, second) = ((b.0 - a.0, b.1 - a.1), (d.0 - c.0, d.1 - c.1));
+    let denominator = first.0 * second.1 - first.1 * second.0;
+    if denominator == 0.0 {
+        return None;
+    }
+    let t = ((c.0 - a.0) * second.1 - (c.1 - a.1) * second.0) / denominator;
+    let u = ((c.0 - a.0) * first.1 - (c.1 - a.1) * first.0) / denominator;
+    if t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0 {
+        Some((a.0 + t * first.0, a.1 + t * first.1))
+    } else {
+        None
+    }
+}
+
+// Edges of every ring cut at the given points, as pieces between point indices
+fn ring_pieces(rings: &[Vec<Vertex>], edges: &[(Vertex, Vertex)], cuts: Vec<Vec<Vertex>>, points: &mut PointIndex)
+               -> Vec<Vec<(usize, usize)>> {
+    let mut cuts = cuts.into_iter();
+    let mut edges = edges.iter();
+    rings.iter().map(|ring| {
+        let mut pieces = Vec::new();
+        for ((a, b), mut cuts) in edges.by_ref().zip(cuts.by_ref()).take(ring.len()) {
+            let along = |p: &Vertex| (p.0 - a.0) * (b.0 - a.0) + (p.1 - a.1) * (b.1 - a.1);
+            cuts.sort_by(|p, q| along(p).total_cmp(&along(q)));
+            let mut indices = vec![points.index(*a)];
+            indices.extend(cuts.iter().map(|cut| points.index(*cut)));
+            indices.push(points.index(*b));
+            indices.dedup();
+            pieces.extend(indices.windows(2).map(|pair| (pair[0], pair[1])));
+        }
+        pieces
+    }).collect()
+}
+
+// Boundaries of both regions with their edges cut wherever the other boundary crosses or touches them, so that
+// every piece lies completely inside, outside or on the boundary of the other region. Pieces are classified by
+// their middle points, and by matching the pieces of the other region for shared edges.
+fn cut_rings(subject: &Region, clip: &Region, points: &mut PointIndex) -> (Vec<Vec<Piece>>, Vec<Vec<Piece>>) {
+    let (subject_rings, clip_rings) = (subject.boundary(), clip.boundary());
+    // Input points come first, so that the points merged with them keep their exact coordinates
+    for point in subject_rings.iter().chain(clip_rings.iter()).flat_map(|ring| ring.iter()) {
+        points.index(*point);
+    }
+    let tolerance = points.tolerance;
+    let (subject_edges, clip_edges) = (edges(&subject_rings), edges(&clip_rings));
+    let mut subject_cuts: Vec<Vec<Vertex>> = vec![Vec::new(); subject_edges.len()];
+    let mut clip_cuts: Vec<Vec<Vertex>> = vec![Vec::new(); clip_edges.len()];
+    for (i, j) in candidate_pairs(&subject_edges, &clip_edges, tolerance).into_iter() {
+        let ((a, b), (c, d)) = (&subject_edges[i], &clip_edges[j]);
+        {
+            let mut touching = false;
+            for point in [*c, *d].iter().filter(|point| distance_to_segment(**point, *a, *b) < tolerance) {
+                subject_cuts[i].push(*point);
+                touching = true;
+            }
+            for point in [*a, *b].iter().filter(|point| distance_to_segment(**point, *c, *d) < tolerance) {
+                clip_cuts[j].push(*point);
+                touching = true;
+            }
+            // Segments touching each other meet only at the touching end points, unless they overlap
+            if !touching {
+                if let Some(point) = crossing(*a, *b, *c, *d) {
+                    subject_cuts[i].push(point);
+                    clip_cuts[j].push(point);
+                }
+            }
+        }
+    }
+    let subject_pieces = ring_pieces(&subject_rings, &subject_edges, subject_cuts, points);
+    let clip_pieces = ring_pieces(&clip_rings, &clip_edges, clip_cuts, points);
+    let points = &points.points;
+    let classify = |pieces: &[Vec<(usize, usize)>], other_pieces: &[Vec<(usize, usize)>], other: &Region| {
+        let other_pieces: HashSet<(usize, usize)> = other_pieces.iter().flat_map(|ring| ring.iter().cloned()).collect();
+        pieces.iter().map(|ring| ring.iter().map(|(from, to)| {
+            let side = if other_pieces.contains(&(*from, *to)) {
+                Side::SharedSame
+            } else if other_pieces.contains(&(*to, *from)) {
+                Side::SharedOpposite
+            } else {
+                let (a, b) = (points[*from], points[*to]);
+                if other.contains(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)) { Side::Inside } else { Side::Outside }
+            };
+            Piece { from: *from, to: *to, side }
+        }).collect()).collect::<Vec<Vec<Piece>>>()
+    };
+    (classify(&subject_pieces, &clip_pieces, clip), classify(&clip_pieces, &subject_pieces, subject))
+}
+
+// Selected pieces of both outlines joined into rings. The walk follows the outline it is on as long as its pieces
+// are selected, at the points where that outline enters or leaves the other region it continues along the other
+// outline. Where several selected pieces start at such a point the sharpest left turn is taken, so that rings
+// touching each other stay separate. Consistent input always closes the rings, chains left open by self
+// intersecting outlines are dropped.
+fn link(outlines: &[Vec<Vec<(Piece, bool)>>; 2], points: &[Vertex]) -> Vec<Vec<Vertex>> {
+    let positions: Vec<(usize, usize, usize)> = outlines.iter().enumerate()
+        .flat_map(|(outline, rings)| rings.iter().enumerate()
+            .flat_map(move |(ring, pieces)| (0..pieces.len()).map(move |index| (outline, ring, index))))
+        .filter(|(outline, ring, index)| outlines[*outline][*ring][*index].1)
+        .collect();
+    let piece = |(outline, ring, index): (usize, usize, usize)| outlines[outline][ring][index].0;
+    let mut starting: HashMap<usize, Vec<(usize, usize, usize)>> = HashMap::new();
+    for position in positions.iter() {
+        starting.entry(piece(*position).from).or_default().push(*position);
+    }
+    let direction = |piece: Piece| (points[piece.to].0 - points[piece.from].0, points[piece.to].1 - points[piece.from].1);
+    let mut visited: HashSet<(usize, usize, usize)> = HashSet::new();
+    let mut rings = Vec::new();
+    for start in positions.iter() {
+        if visited.contains(start) {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut current = *start;
+        loop {
+            visited.insert(current);
+            ring.push(points[piece(current).from]);
+            let to = piece(current).to;
+            if to == piece(*start).from {
+                rings.push(ring);
+                break;
+            }
+            let (outline, ring_index, index) = current;
+            let following = (outline, ring_index, (index + 1) % outlines[outline][ring_index].len());
+            let next = if outlines[outline][ring_index][following.2].1 && !visited.contains(&following) {
+                Some(following)
+            } else {
+                let incoming = direction(piece(current));
+                let turn = |position: &&(usize, usize, usize)| {
+                    let outgoing = direction(piece(**position));
+                    (incoming.0 * outgoing.1 - incoming.1 * outgoing.0).atan2(incoming.0 * outgoing.0 + incoming.1 * outgoing.1)
+                };
+                starting.get(&to)
+                    .and_then(|candidates| candidates.iter().filter(|position| !visited.contains(*position))
+                        .max_by(|a, b| turn(a).total_cmp(&turn(b))))
+                    .cloned()
+            };
+            match next {
+                Some(next) => current = next,
+                None => {
+                    debug_assert!(false, "open chain of boolean operation pieces, the outlines intersect themselves");
+                    break;
+                }
+            }
+        }
+    }
+    rings
+}
+
+// Points lying on the line through their neighbours are removed, which also removes spikes of zero width
+fn simplified(mut ring: Vec<Vertex>, tolerance: f64) -> Vec<Vertex> {
+    let mut index = 0;
+    let mut unchanged = 0;
+    while ring.len() >= 3 && unchanged < ring.len() {
+        let length = ring.len();
+        let (previous, point, next) = (ring[(index + length - 1) % length], ring[index % length], ring[(index + 1) % length]);
+        let base = (next.0 - previous.0).hypot(next.1 - previous.1);
+        let offset = ((next.0 - previous.0) * (point.1 - previous.1) - (next.1 - previous.1) * (point.0 - previous.0)).abs();
+        if offset <= tolerance * base {
+            ring.remove(index % length);
+            unchanged = 0;
+        } else {
+            index = (index + 1) % length;
+            unchanged += 1;
+        }
+    }
+    ring
+}
+
+fn tolerance(subject: &Region, clip: &Region) -> f64 {
+    let magnitude = subject.rings.iter().chain(clip.rings.iter()).flat_map(|ring| ring.iter())
+        .fold(1.0f64, |magnitude, (x, y)| magnitude.max(x.abs()).max(y.abs()));
+    TOUCH_EPSILON * magnitude
+}
+
+// Weiler-Atherton traversal of the boundaries of two regions. Edges of both are cut where the boundaries cross
+// or touch, which keeps outlines sharing edges and vertices exact. The result is bounded by the pieces lying
+// inside the other region when keep_inside is set, outside of it otherwise, and by the edges shared with the
+// same interior side, taken from the subject. Points of the input are kept exact, rings without area are
+// left out.
+fn traverse(subject: &Region, clip: &Region, keep_inside: bool, tolerance: f64) -> Vec<Vec<Vertex>> {
+    let mut points = PointIndex::new(tolerance);
+    let (subject_pieces, clip_pieces) = cut_rings(subject, clip, &mut points);
+    let wanted = if keep_inside { Side::Inside } else { Side::Outside };
+    let select = |rings: Vec<Vec<Piece>>, shared: bool| -> Vec<Vec<(Piece, bool)>> {
+        rings.into_iter()
+            .map(|ring| ring.into_iter().map(|piece| (piece, piece.side == wanted || (shared && piece.side == Side::SharedSame))).collect())
+            .collect()
+    };
+    let outlines = [select(subject_pieces, true), select(clip_pieces, false)];
+    link(&outlines, &points.points).into_iter()
+        .map(|ring| simplified(ring, tolerance))
+        .filter(|ring| ring.len() >= 3)
+        .collect()
+}
+
+// Counter clockwise rings become outlines, clockwise ones holes of the smallest outline containing them
+fn assemble(rings: Vec<Vec<Vertex>>, template: &Polygon, tolerance: f64) -> Vec<Polygon> {
+    let mut outlines: Vec<(f64, Vec<Vertex>, Vec<Vec<Point2d>>)> = Vec::new();
+    let mut holes = Vec::new();
+    for ring in rings.into_iter().filter(|ring| ring.len() >= 3) {
+        let area = signed_area(&ring);
+        if area > 0.0 {
+            outlines.push((area, ring, Vec::new()));
+        } else if area < 0.0 {
+            holes.push(ring);
+        }
+    }
+    // Holes may touch their outline, a vertex or edge middle of the hole away from it decides
+    for hole in holes.iter() {
+        let length = hole.len();
+        let candidates: Vec<Vertex> = hole.iter().cloned()
+            .chain((0..length).map(|i| ((hole[i].0 + hole[(i + 1) % length].0) / 2.0, (hole[i].1 + hole[(i + 1) % length].1) / 2.0)))
+            .collect();
+        let outline = outlines.iter_mut()
+            .filter(|(_, outline, _)| {
+                let on_outline = |point: &&Vertex| edges(std::slice::from_ref(outline)).iter()
+                    .any(|(a, b)| distance_to_segment(**point, *a, *b) < tolerance);
+                let point = candidates.iter().find(|point| !on_outline(point)).unwrap_or(&hole[0]);
+                winding_number(*point, outline) != 0
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some((_, _, outline_holes)) = outline {
+            outline_holes.push(to_points(hole));
+        }
+    }
+    outlines.into_iter()
+        .map(|(_, outline, holes)| Polygon { points: to_points(&outline), holes, ..template.clone() })
+        .collect()
+}
+
+// Works for concave polygons with holes, every disjoint piece of the result is a separate polygon
+// with holes of its own. Pieces get the attributes of the subject. Both polygons cover the area they
+// are drawn with according to their fill rules, their rings must not intersect themselves or each other.
+pub fn boolean_operation(subject: &Polygon, clip: &Polygon, operation: BooleanOperation) -> Vec<Polygon> {
+    let (subject_region, clip_region) = (Region::new(subject), Region::new(clip));
+    let tolerance = tolerance(&subject_region, &clip_region);
+    let rings = match operation {
+        BooleanOperation::Intersection => {
+            traverse(&subject_region, &clip_region, true, tolerance)
+        }
+        BooleanOperation::Union => {
+            traverse(&subject_region, &clip_region, false, tolerance)
+        }
+        // Intersection with the complement of the clip region, whose boundary runs the other way
+        BooleanOperation::Difference => {
+            traverse(&subject_region, &clip_region.complement(), true, tolerance)
+        }
+        BooleanOperation::Xor => {
+            let mut pieces = boolean_operation(subject, clip, BooleanOperation::Difference);
+            let remainder = boolean_operation(clip, subject, BooleanOperation::Difference);
+            pieces.extend(remainder.into_iter().map(|piece| Polygon { points: piece.points, holes: piece.holes, ..subject.clone() }));
+            return pieces;
+        }
+    };
+    assemble(rings, subject, tolerance)
+}
@@ -0,0 +1,96 @@
+use crate::boolean::{BooleanOperation, boolean_operation};
+use crate::draw::Draw;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::{FillRule, Polygon};
+use crate::golden_tests::assert_golden;
+
+fn ring_area(points: &[Point2d]) -> f32 {
+    let length = points.len();
+    ((0..length).map(|i| points[i].x * points[(i + 1) % length].y - points[(i + 1) % length].x * points[i].y).sum::<f32>() / 2.0).abs()
+}
+
+fn area(polygons: &[Polygon]) -> f32 {
+    polygons.iter().map(|polygon| ring_area(&polygon.points) - polygon.holes.iter().map(|hole| ring_area(hole)).sum::<f32>()).sum()
+}
+
+#[test]
+fn boolean_operations() {
+    let offset = |points: &[(f32, f32)], x: f32, y: f32| -> Vec<Point2d> {
+        points.iter().map(|(px, py)| Point2d::new(px + x, py + y)).collect()
+    };
+    // Frame with a hole and a concave hook crossing both of its outlines
+    let frame = |x: f32, y: f32| Polygon::new(PixelColor::rgb(0, 255, 255), offset(&[(3.0, 3.0), (21.0, 3.0), (21.0, 21.0), (3.0, 21.0)], x, y))
+        .with_holes(vec![offset(&[(8.0, 8.0), (8.0, 16.0), (16.0, 16.0), (16.0, 8.0)], x, y)]);
+    let hook = |x: f32, y: f32| Polygon::new(PixelColor::rgb(255, 0, 255), offset(&[(12.0, 12.0), (29.0, 12.0), (29.0, 29.0), (12.0, 29.0),
+                                                                                    (12.0, 24.0), (24.0, 24.0), (24.0, 17.0), (12.0, 17.0)], x, y));
+    let operations = [(BooleanOperation::Union, 0.0, 32.0), (BooleanOperation::Intersection, 32.0, 32.0),
+                      (BooleanOperation::Difference, 0.0, 0.0), (BooleanOperation::Xor, 32.0, 0.0)];
+    let mut drawables: Vec<Box<dyn Draw>> = Vec::new();
+    let mut areas = Vec::new();
+    for (operation, x, y) in operations.iter() {
+        let pieces = boolean_operation(&frame(*x, *y), &hook(*x, *y), *operation);
+        areas.push(area(&pieces));
+        drawables.extend(pieces.into_iter().map(|piece| Box::new(piece) as Box<dyn Draw>));
+    }
+    assert_golden("boolean_operations", drawables);
+    let (frame_area, hook_area) = (area(&[frame(0.0, 0.0)]), area(&[hook(0.0, 0.0)]));
+    let (union, intersection, difference, xor) = (areas[0], areas[1], areas[2], areas[3]);
+    let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+    assert!(close(union + intersection, frame_area + hook_area), "areas {:?}", areas);
+    assert!(close(difference, frame_area - intersection), "areas {:?}", areas);
+    assert!(close(xor, union - intersection), "areas {:?}", areas);
+}
+
+#[test]
+fn boolean_operations_on_touching_outlines() {
+    let square = |x: f32, y: f32| Polygon::new(PixelColor::red(), vec![Point2d::new(x, y), Point2d::new(x + 20.0, y),
+                                                                       Point2d::new(x + 20.0, y + 20.0), Point2d::new(x, y + 20.0)]);
+    let (left, right) = (square(10.0, 10.0), square(30.0, 10.0));
+    let corners = |pieces: &[Polygon]| -> Vec<Vec<(f32, f32)>> {
+        pieces.iter().map(|piece| {
+            let mut points: Vec<(f32, f32)> = piece.points.iter().map(|p| (p.x, p.y)).collect();
+            points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            points
+        }).collect()
+    };
+    for operation in [BooleanOperation::Union, BooleanOperation::Intersection].iter() {
+        assert_eq!(corners(&boolean_operation(&left, &left, *operation)), corners(std::slice::from_ref(&left)), "{:?}", operation);
+    }
+    assert!(boolean_operation(&left, &left, BooleanOperation::Difference).is_empty());
+    assert!(boolean_operation(&left, &left, BooleanOperation::Xor).is_empty());
+    let both = Polygon::new(PixelColor::red(), vec![Point2d::new(10.0, 10.0), Point2d::new(50.0, 10.0),
+                                                    Point2d::new(50.0, 30.0), Point2d::new(10.0, 30.0)]);
+    assert_eq!(corners(&boolean_operation(&left, &right, BooleanOperation::Union)), corners(std::slice::from_ref(&both)));
+    assert_eq!(corners(&boolean_operation(&right, &left, BooleanOperation::Union)), corners(&[both]));
+    assert!(boolean_operation(&left, &right, BooleanOperation::Intersection).is_empty());
+    assert_eq!(corners(&boolean_operation(&right, &left, BooleanOperation::Difference)), corners(std::slice::from_ref(&right)));
+    assert_eq!(area(&boolean_operation(&left, &right, BooleanOperation::Xor)), 800.0);
+    // Squares touching at a corner stay separate pieces, the overlap of a square inside another shares its edges
+    let corner = square(30.0, 30.0);
+    assert_eq!(boolean_operation(&left, &corner, BooleanOperation::Union).len(), 2);
+    assert!(boolean_operation(&left, &corner, BooleanOperation::Intersection).is_empty());
+    let inner = Polygon::new(PixelColor::red(), vec![Point2d::new(10.0, 10.0), Point2d::new(20.0, 10.0),
+                                                     Point2d::new(20.0, 20.0), Point2d::new(10.0, 20.0)]);
+    assert_eq!(corners(&boolean_operation(&left, &inner, BooleanOperation::Intersection)), corners(std::slice::from_ref(&inner)));
+    let remainder = boolean_operation(&left, &inner, BooleanOperation::Difference);
+    assert_eq!(remainder.len(), 1);
+    assert_eq!(area(&remainder), 300.0);
+}
+
+#[test]
+fn boolean_operations_follow_fill_rules() {
+    let ring = |from: f32, to: f32| vec![Point2d::new(from, from), Point2d::new(to, from), Point2d::new(to, to), Point2d::new(from, to)];
+    // The inner ring runs the same way as the outer one, it is a hole only under the even-odd rule
+    let even_odd = Polygon::new(PixelColor::red(), ring(10.0, 30.0)).with_holes(vec![ring(15.0, 25.0)]);
+    let non_zero = even_odd.with_fill_rule(FillRule::NonZero);
+    let cover = Polygon::new(PixelColor::red(), ring(0.0, 20.0));
+    assert_eq!(area(&boolean_operation(&even_odd, &cover, BooleanOperation::Intersection)), 75.0);
+    assert_eq!(area(&boolean_operation(&non_zero, &cover, BooleanOperation::Intersection)), 100.0);
+    assert_eq!(area(&boolean_operation(&cover, &even_odd, BooleanOperation::Difference)), 325.0);
+    assert_eq!(area(&boolean_operation(&cover, &non_zero, BooleanOperation::Difference)), 300.0);
+    let union = boolean_operation(&non_zero, &cover, BooleanOperation::Union);
+    assert_eq!(union.len(), 1);
+    assert!(union[0].holes.is_empty());
+    assert_eq!(area(&union), 700.0);
+}
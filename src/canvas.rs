@@ -0,0 +1,125 @@
+use crate::compositing::Compositing;
+use crate::pixel_color::PixelColor;
+
+#[derive(Copy, Clone)]
+pub struct PixelFormat {
+    pub red_shift: u8,
+    pub green_shift: u8,
+    pub blue_shift: u8,
+    pub alpha_shift: u8,
+    pub has_alpha: bool
+}
+
+impl PixelFormat {
+    pub fn new(red_shift: u8, green_shift: u8, blue_shift: u8, alpha_shift: u8) -> PixelFormat {
+        PixelFormat { red_shift, green_shift, blue_shift, alpha_shift, has_alpha: true }
+    }
+
+    // Window surfaces usually come without an alpha channel, their pixels are always opaque
+    pub fn without_alpha(red_shift: u8, green_shift: u8, blue_shift: u8) -> PixelFormat {
+        PixelFormat { red_shift, green_shift, blue_shift, alpha_shift: 0, has_alpha: false }
+    }
+
+    pub fn argb8888() -> PixelFormat {
+        PixelFormat::new(16, 8, 0, 24)
+    }
+
+    pub fn encode(&self, color: &PixelColor) -> u32 {
+        let alpha = if self.has_alpha { (color.alpha as u32) << (self.alpha_shift as u32) } else { 0 };
+        (color.red as u32) << (self.red_shift as u32) |
+            (color.blue as u32) << (self.blue_shift as u32) |
+            (color.green as u32) << (self.green_shift as u32) |
+            alpha
+    }
+
+    pub fn decode(&self, value: u32) -> PixelColor {
+        PixelColor {
+            red: (value >> (self.red_shift as u32)) as u8,
+            green: (value >> (self.green_shift as u32)) as u8,
+            blue: (value >> (self.blue_shift as u32)) as u8,
+            alpha: if self.has_alpha { (value >> (self.alpha_shift as u32)) as u8 } else { 255 }
+        }
+    }
+}
+
+// Pixels are stored row by row starting from the top of the screen,
+// while y coordinates passed to put_pixel grow from the bottom
+pub trait Canvas {
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    fn format(&self) -> &PixelFormat;
+    fn pixels(&self) -> &[u32];
+    fn pixels_mut(&mut self) -> &mut [u32];
+
+    fn calculate_color_value(&self, color: &PixelColor) -> u32 {
+        self.format().encode(color)
+    }
+
+    fn clean_color(&mut self, color: &PixelColor) {
+        let value = self.calculate_color_value(color);
+        for pixel in self.pixels_mut().iter_mut() {
+            *pixel = value;
+        }
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width() && (y as u32) < self.height()
+    }
+
+    fn get_pixel(&self, x: i32, y: i32) -> Option<PixelColor> {
+        if self.contains(x, y) {
+            let offset = ((self.height() - y as u32 - 1) * self.width() + x as u32) as usize;
+            Some(self.format().decode(self.pixels()[offset]))
+        } else {
+            None
+        }
+    }
+
+    // Pixels outside of the canvas are silently skipped
+    fn put_pixel(&mut self, x: i32, y: i32, color: &PixelColor) {
+        if self.contains(x, y) {
+            self.put_pixel_in_bounds(x as u32, y as u32, color);
+        }
+    }
+
+    fn put_pixel_f(&mut self, x: f32, y: f32, color: &PixelColor) {
+        if x.is_finite() && y.is_finite() {
+            self.put_pixel(x.floor() as i32, y.floor() as i32, color);
+        }
+    }
+
+    // Source-over blending of color scaled by coverage in range [0, 1]
+    fn blend_pixel(&mut self, x: i32, y: i32, color: &PixelColor, coverage: f32) {
+        self.composite_pixel(x, y, color, coverage, &Compositing::source_over());
+    }
+
+    fn composite_pixel(&mut self, x: i32, y: i32, color: &PixelColor, coverage: f32, compositing: &Compositing) {
+        if compositing.is_opaque_copy(color, coverage) {
+            self.put_pixel(x, y, color);
+        } else if let Some(destination) = self.get_pixel(x, y) {
+            let result = compositing.composite(color, &destination, coverage);
+            self.put_pixel_in_bounds(x as u32, y as u32, &result);
+        }
+    }
+
+    // Composites pixels from x_from to x_to inclusive, the part outside of the canvas is skipped
+    fn fill_span(&mut self, y: i32, x_from: i32, x_to: i32, color: &PixelColor, compositing: &Compositing) {
+        if y < 0 || y as u32 >= self.height() {
+            return;
+        }
+        let first = x_from.max(0);
+        let last = x_to.min(self.width() as i32 - 1);
+        for x in first..(last + 1) {
+            self.composite_pixel(x, y, color, 1.0, compositing);
+        }
+    }
+
+    // For callers that already checked the coordinates, panics instead of skipping pixels outside of the canvas,
+    // so a wrong offset never ends up in a neighbouring row
+    fn put_pixel_in_bounds(&mut self, x: u32, y: u32, color: &PixelColor) {
+        assert!(x < self.width() && y < self.height(), "pixel ({}, {}) outside of the canvas", x, y);
+        let offset = ((self.height() - y - 1) * self.width() + x) as usize;
+        let value = self.calculate_color_value(color);
+        self.pixels_mut()[offset] = value;
+    }
+}
@@ -0,0 +1,29 @@
+use crate::canvas::Canvas;
+use crate::line::Line;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden};
+
+#[test]
+fn point() {
+    assert_golden("point", vec![Box::new(Point2d::new(5.2, 7.8)), Box::new(Point2d::new(60.0, 60.0))]);
+}
+
+#[test]
+fn offscreen_primitives() {
+    assert_golden("offscreen_primitives", vec![
+        Box::new(Polygon::new(PixelColor::red(), vec![Point2d::new(40.0, 40.0), Point2d::new(70.0, 100.0),
+                                                      Point2d::new(90.0, 50.0)])),
+        Box::new(Point2d::new(-0.5, 3.0)),
+        Box::new(Point2d::new(3.0, 64.0)),
+        Box::new(Line::new(10.0, 50.0, 20.0, 80.0))
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "outside of the canvas")]
+fn put_pixel_in_bounds_rejects_offscreen_pixels() {
+    MemoryCanvas::new(WIDTH, HEIGHT).put_pixel_in_bounds(WIDTH, 0, &PixelColor::red());
+}
@@ -0,0 +1,116 @@
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+
+#[derive(Copy, Clone)]
+pub struct Circle {
+    pub center: Point2d,
+    pub radius: f32,
+    pub color: PixelColor,
+    pub filled: bool,
+    pub compositing: Compositing
+}
+
+impl Circle {
+    pub fn new(color: PixelColor, center: Point2d, radius: f32) -> Circle {
+        Circle { center, radius, color, filled: false, compositing: Compositing::source_over() }
+    }
+
+    pub fn filled(color: PixelColor, center: Point2d, radius: f32) -> Circle {
+        Circle { filled: true, ..Circle::new(color, center, radius) }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Circle {
+        Circle { compositing, ..*self }
+    }
+
+    // Midpoint circle algorithm. In the second octant it keeps, for every x, the largest y with
+    // x^2 + y^2 - y < r^2, so the pixels of a single row follow from that condition directly and only
+    // the rows of the canvas are rasterized, whatever the radius. Returns the half widths of the row
+    // relative to the center: a run of the flat part of the octant and a single pixel of the steep part.
+    fn row_pixels(radius: i128, row: i128) -> (Option<(i128, i128)>, Option<i128>) {
+        let row = row.abs();
+        // The algorithm starts with the point (0, r) even when the condition does not hold for it
+        if radius == 0 {
+            return (if row == 0 { Some((0, 0)) } else { None }, None);
+        }
+        let radius2 = radius * radius;
+        // Largest column of the octant with y at least the given row
+        let last_column = |row: i128| {
+            let limit = (radius - row) * (radius + row) + row;
+            if limit > 0 { Some(integer_sqrt(limit - 1)) } else { None }
+        };
+        let run = last_column(row).and_then(|to| {
+            let from = last_column(row + 1).map_or(0, |column| column + 1);
+            let to = to.min(row);
+            if from <= to { Some((from, to)) } else { None }
+        });
+        let remainder = radius2 - row * row;
+        let steep = if remainder > 0 {
+            let root = integer_sqrt(remainder);
+            let y = if root * root + root < remainder { root + 1 } else { root };
+            if row <= y { Some(y) } else { None }
+        } else {
+            None
+        };
+        (run, steep)
+    }
+}
+
+fn integer_sqrt(value: i128) -> i128 {
+    let mut root = (value as f64).sqrt() as i128;
+    while root * root > value {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= value {
+        root += 1;
+    }
+    root
+}
+
+impl Draw for Circle {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        if !(self.center.x.is_finite() && self.center.y.is_finite() && self.radius.is_finite()) || self.radius < 0.0 {
+            return;
+        }
+        let center_x = self.center.x.floor() as i64;
+        let center_y = self.center.y.floor() as i64;
+        // Limited so the row computations fit into 128 bits, far beyond anything visible at pixel resolution
+        let radius = (self.radius.round() as i64).min(1 << 61) as i128;
+        let (width, height) = (canvas.width() as i64, canvas.height() as i64);
+        for y in 0..height {
+            let (run, steep) = Circle::row_pixels(radius, y as i128 - center_y as i128);
+            if self.filled {
+                let half_width = match (run, steep) {
+                    (_, Some(steep)) => steep,
+                    (Some((_, to)), None) => to,
+                    (None, None) => continue
+                };
+                let left = (center_x as i128 - half_width).max(-1) as i32;
+                let right = (center_x as i128 + half_width).min(width as i128) as i32;
+                canvas.fill_span(y as i32, left, right, &self.color, &self.compositing);
+            } else {
+                // Both halves of the row share the pixels on the vertical axis, every pixel is composited once
+                let mut columns: Vec<i64> = Vec::new();
+                if let Some((from, to)) = run {
+                    for sign in [1, -1].iter() {
+                        let first = (sign * from + center_x as i128).clamp(-1, width as i128) as i64;
+                        let last = (sign * to + center_x as i128).clamp(-1, width as i128) as i64;
+                        columns.extend(first.min(last)..(first.max(last) + 1));
+                    }
+                }
+                if let Some(steep) = steep {
+                    columns.extend([center_x as i128 + steep, center_x as i128 - steep].iter()
+                        .map(|column| (*column).clamp(-1, width as i128) as i64));
+                }
+                columns.sort_unstable();
+                columns.dedup();
+                for x in columns.iter().filter(|x| **x >= 0 && **x < width) {
+                    canvas.composite_pixel(*x as i32, y as i32, &self.color, 1.0, &self.compositing);
+                }
+            }
+        }
+    }
+}
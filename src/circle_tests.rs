@@ -0,0 +1,42 @@
+use std::f32::consts::FRAC_PI_2;
+use crate::canvas::Canvas;
+use crate::circle::Circle;
+use crate::draw::Draw;
+use crate::ellipse::Ellipse;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden};
+
+#[test]
+fn circles_and_ellipses() {
+    assert_golden("circles_and_ellipses", vec![
+        Box::new(Circle::filled(PixelColor::rgb(0, 0, 255), Point2d::new(14.0, 14.0), 10.0)),
+        Box::new(Circle::new(PixelColor::red(), Point2d::new(14.0, 14.0), 12.0)),
+        Box::new(Ellipse::new(PixelColor::rgb(0, 255, 0), Point2d::new(46.0, 14.0), 15.0, 7.0)),
+        Box::new(Ellipse::filled(PixelColor::rgb(255, 255, 0), Point2d::new(46.0, 14.0), 4.0, 11.0).with_rotation(FRAC_PI_2)),
+        Box::new(Ellipse::filled(PixelColor::rgb(0, 255, 255), Point2d::new(16.0, 46.0), 14.0, 6.0).with_rotation(0.6)),
+        Box::new(Ellipse::new(PixelColor::rgb(255, 0, 255), Point2d::new(46.0, 46.0), 15.0, 8.0).with_rotation(-0.9)),
+        Box::new(Circle::new(PixelColor::red(), Point2d::new(60.0, 60.0), 8.0))
+    ]);
+}
+
+// Radii far beyond the canvas are rasterized only in its rows
+#[test]
+fn huge_circles_and_ellipses() {
+    let red_rows = |canvas: &MemoryCanvas| -> Vec<i32> {
+        (0..HEIGHT as i32).filter(|y| (0..WIDTH as i32).all(|x| canvas.get_pixel(x, *y) == Some(PixelColor::red()))).collect()
+    };
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Circle::filled(PixelColor::red(), Point2d::new(32.0, 32.0), 3e9).draw(&mut canvas);
+    assert_eq!(red_rows(&canvas).len(), HEIGHT as usize);
+    // Topmost run of the outline touches the bottom row
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Circle::new(PixelColor::red(), Point2d::new(32.0, -2147483648.0), 2147483648.0).draw(&mut canvas);
+    Ellipse::new(PixelColor::red(), Point2d::new(32.0, 32.0), 3e9, 2e9).with_rotation(0.3).draw(&mut canvas);
+    assert_eq!(red_rows(&canvas), vec![0]);
+    assert_eq!(canvas.get_pixel(32, 1), Some(PixelColor::new(0, 0, 0, 0)));
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Ellipse::filled(PixelColor::red(), Point2d::new(32.0, 32.0), 1e30, 4.0).draw(&mut canvas);
+    assert_eq!(red_rows(&canvas), (28..36).collect::<Vec<i32>>());
+}
@@ -1,5 +1,6 @@
 use float_cmp::approx_eq;
 use sdl2::libc::clone;
+use crate::boolean::{BooleanOperation, boolean_operation};
 use crate::line::Line;
 use crate::point2d::Point2d;
 use crate::polygon::Polygon;
@@ -77,10 +78,10 @@ pub fn cohen_sutherland_line_clip(line: &Line, rectangle: &ClippingRectangle) ->
             }
 
             if out_code == first_out_code {
-                result = result.map(|l| Line::new(x, y, l.last.x, l.last.y));
+                result = result.map(|l| Line { first: Point2d::new(x, y), ..l });
                 first_out_code = compute_out_code(&Point2d::new(x, y), rectangle);
             } else {
-                result = result.map(|l| Line::new(l.first.x, l.first.y, x, y));
+                result = result.map(|l| Line { last: Point2d::new(x, y), ..l });
                 last_out_code = compute_out_code(&Point2d::new(x, y), rectangle);
             }
         }
@@ -88,6 +89,130 @@ pub fn cohen_sutherland_line_clip(line: &Line, rectangle: &ClippingRectangle) ->
     result
 }
 
+#[derive(Copy, Clone)]
+enum Boundary {
+    Left,
+    Right,
+    Bottom,
+    Top
+}
+
+// Coordinate of the crossed boundary is taken exactly, the other one from the line parameter
+fn boundary_point(line: &Line, rectangle: &ClippingRectangle, boundary: Boundary, t: f32) -> Point2d {
+    let x = line.first.x + (line.last.x - line.first.x) * t;
+    let y = line.first.y + (line.last.y - line.first.y) * t;
+    match boundary {
+        Boundary::Left => Point2d::new(rectangle.x_min, y),
+        Boundary::Right => Point2d::new(rectangle.x_max, y),
+        Boundary::Bottom => Point2d::new(x, rectangle.y_min),
+        Boundary::Top => Point2d::new(x, rectangle.y_max)
+    }
+}
+
+pub fn liang_barsky_line_clip(line: &Line, rectangle: &ClippingRectangle) -> Option<Line> {
+    let delta_x = line.last.x - line.first.x;
+    let delta_y = line.last.y - line.first.y;
+    let constraints = [(-delta_x, line.first.x - rectangle.x_min, Boundary::Left),
+                       (delta_x, rectangle.x_max - line.first.x, Boundary::Right),
+                       (-delta_y, line.first.y - rectangle.y_min, Boundary::Bottom),
+                       (delta_y, rectangle.y_max - line.first.y, Boundary::Top)];
+    let mut t_entering: f32 = 0.0;
+    let mut t_leaving: f32 = 1.0;
+    let mut entering = None;
+    let mut leaving = None;
+    for (p, q, boundary) in constraints.iter() {
+        if *p == 0.0 {
+            // Parallel to the boundary and on its outer side
+            if *q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if *p < 0.0 {
+                if t > t_leaving {
+                    return None;
+                }
+                if t > t_entering {
+                    t_entering = t;
+                    entering = Some(*boundary);
+                }
+            } else {
+                if t < t_entering {
+                    return None;
+                }
+                if t < t_leaving {
+                    t_leaving = t;
+                    leaving = Some(*boundary);
+                }
+            }
+        }
+    }
+    let first = entering.map_or(line.first, |boundary| boundary_point(line, rectangle, boundary, t_entering));
+    let last = leaving.map_or(line.last, |boundary| boundary_point(line, rectangle, boundary, t_leaving));
+    Some(Line { first, last, ..*line })
+}
+
+// Point where the segment going from the outside point towards the other one enters the rectangle.
+// When the outside point lies in a corner region, the direction is compared with the ray to the corner
+// to select the crossed edge, so only one intersection is computed
+fn nicholl_lee_nicholl_crossing(outside: &Point2d, other: &Point2d, out_code: u8, rectangle: &ClippingRectangle) -> Option<Point2d> {
+    let delta_x = other.x - outside.x;
+    let delta_y = other.y - outside.y;
+    let x_boundary = if out_code & LEFT != 0 {
+        Some(rectangle.x_min)
+    } else if out_code & RIGHT != 0 {
+        Some(rectangle.x_max)
+    } else {
+        None
+    };
+    let y_boundary = if out_code & BOTTOM != 0 {
+        Some(rectangle.y_min)
+    } else if out_code & TOP != 0 {
+        Some(rectangle.y_max)
+    } else {
+        None
+    };
+    let crosses_x_boundary = match (x_boundary, y_boundary) {
+        (Some(x), Some(y)) => ((x - outside.x) * delta_y - (y - outside.y) * delta_x) * (delta_x * delta_y) >= 0.0,
+        (Some(_), None) => true,
+        _ => false
+    };
+    if crosses_x_boundary {
+        let x = x_boundary.unwrap();
+        let y = line_passing_two_points_y(outside, other, x);
+        if y < rectangle.y_min || y > rectangle.y_max {
+            return None;
+        }
+        Some(Point2d::new(x, y))
+    } else {
+        let y = y_boundary.unwrap();
+        let x = line_passing_two_points_x(outside, other, y);
+        if x < rectangle.x_min || x > rectangle.x_max {
+            return None;
+        }
+        Some(Point2d::new(x, y))
+    }
+}
+
+pub fn nicholl_lee_nicholl_line_clip(line: &Line, rectangle: &ClippingRectangle) -> Option<Line> {
+    let first_out_code = compute_out_code(&line.first, rectangle);
+    let last_out_code = compute_out_code(&line.last, rectangle);
+    if (first_out_code & last_out_code) != 0 {
+        return None;
+    }
+    let first = if first_out_code == 0 {
+        line.first
+    } else {
+        nicholl_lee_nicholl_crossing(&line.first, &line.last, first_out_code, rectangle)?
+    };
+    let last = if last_out_code == 0 {
+        line.last
+    } else {
+        nicholl_lee_nicholl_crossing(&line.last, &line.first, last_out_code, rectangle)?
+    };
+    Some(Line { first, last, ..*line })
+}
+
 // Counter clockwise clipping_polygon edges
 pub fn cyrus_beck_line_clip(line: &Line, clipping_polygon: &Vec<Point2d>) -> Option<Line> {
     let mut result = None;
@@ -128,7 +253,7 @@ pub fn cyrus_beck_line_clip(line: &Line, clipping_polygon: &Vec<Point2d>) -> Opt
                 let t_leaving_scaled = &segment_vector * t_leaving;
                 let result_from = (&p0 + &t_entering_scaled).get_to();
                 let result_to = (&p0 + &t_leaving_scaled).get_to();
-                result = Some(Line::new(result_from.x, result_from.y, result_to.x, result_to.y));
+                result = Some(Line { first: result_from, last: result_to, ..*line });
             }
         }
     }
@@ -189,13 +314,13 @@ fn sutherland_hodgman_polygon_edge_clip(polygon: &Polygon, clipping_edge: (&Poin
         }
     }
     if result.len() > 0 {
-        Some(Polygon::new(polygon.color.clone(), result))
+        Some(Polygon { points: result, ..polygon.clone() })
     } else {
         None
     }
 }
 
-pub fn sutherland_hodgman_polygon_clip(polygon: &Polygon, clipping_polygon: &Vec<Point2d>) -> Option<Polygon> {
+fn sutherland_hodgman_ring_clip(polygon: &Polygon, clipping_polygon: &Vec<Point2d>) -> Option<Polygon> {
     let length = clipping_polygon.len();
     let mut result: Option<Polygon> = Some(polygon.clone());
     for i in 0..length {
@@ -212,4 +337,78 @@ pub fn sutherland_hodgman_polygon_clip(polygon: &Polygon, clipping_polygon: &Vec
         }
     }
     result
-}
\ No newline at end of file
+}
+
+// Holes are clipped separately, parts of them left on the clipping edges cancel out with the outline
+pub fn sutherland_hodgman_polygon_clip(polygon: &Polygon, clipping_polygon: &Vec<Point2d>) -> Option<Polygon> {
+    let outline = sutherland_hodgman_ring_clip(&Polygon { holes: Vec::new(), ..polygon.clone() }, clipping_polygon)?;
+    let holes = polygon.holes.iter()
+        .filter_map(|hole| sutherland_hodgman_ring_clip(&Polygon { points: hole.clone(), ..outline.clone() }, clipping_polygon))
+        .map(|hole| hole.points)
+        .collect();
+    Some(Polygon { holes, ..outline })
+}
+
+// Accepts concave subject and clipping polygons of any orientation, every disjoint piece of the
+// intersection is returned as a separate polygon with the attributes of the subject. The intersection
+// runs the Weiler-Atherton traversal shared with the other boolean operations.
+pub fn weiler_atherton_polygon_clip(polygon: &Polygon, clipping_polygon: &[Point2d]) -> Vec<Polygon> {
+    let clip = Polygon::new(polygon.color, clipping_polygon.to_vec());
+    boolean_operation(polygon, &clip, BooleanOperation::Intersection)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ClipSide {
+    Inside,
+    Outside
+}
+
+// Clipping polygon may be concave and have holes, inside is decided by its fill rule.
+// Returns all pieces of the line lying on the requested side, in order from the first point.
+pub fn polygon_line_clip(line: &Line, clipping_polygon: &Polygon, side: ClipSide) -> Vec<Line> {
+    let (a, b) = (line.first, line.last);
+    let direction = (b.x as f64 - a.x as f64, b.y as f64 - a.y as f64);
+    let mut parameters = vec![0.0, 1.0];
+    for ring in std::iter::once(&clipping_polygon.points).chain(clipping_polygon.holes.iter()) {
+        let length = ring.len();
+        for i in 0..length {
+            let (c, d) = (ring[i], ring[(i + 1) % length]);
+            let edge = (d.x as f64 - c.x as f64, d.y as f64 - c.y as f64);
+            let denominator = direction.0 * edge.1 - direction.1 * edge.0;
+            if denominator == 0.0 {
+                continue;
+            }
+            let offset = (c.x as f64 - a.x as f64, c.y as f64 - a.y as f64);
+            let t = (offset.0 * edge.1 - offset.1 * edge.0) / denominator;
+            let u = (offset.0 * direction.1 - offset.1 * direction.0) / denominator;
+            if t > 0.0 && t < 1.0 && (0.0..=1.0).contains(&u) {
+                parameters.push(t);
+            }
+        }
+    }
+    parameters.sort_by(|x, y| x.total_cmp(y));
+    let point_at = |t: f64| Point2d::new((a.x as f64 + direction.0 * t) as f32, (a.y as f64 + direction.1 * t) as f32);
+    // Pieces on the same side meeting at a vertex of the clipping polygon are joined
+    let mut pieces: Vec<(f64, f64)> = Vec::new();
+    for pair in parameters.windows(2) {
+        if pair[1] <= pair[0] {
+            continue;
+        }
+        let inside = clipping_polygon.contains(&point_at((pair[0] + pair[1]) / 2.0));
+        if inside != (side == ClipSide::Inside) {
+            continue;
+        }
+        match pieces.last_mut() {
+            Some(last) if last.1 == pair[0] => last.1 = pair[1],
+            _ => pieces.push((pair[0], pair[1]))
+        }
+    }
+    // End points of the line are kept exact
+    pieces.iter()
+        .map(|(from, to)| Line {
+            first: if *from == 0.0 { a } else { point_at(*from) },
+            last: if *to == 1.0 { b } else { point_at(*to) },
+            ..*line
+        })
+        .collect()
+}
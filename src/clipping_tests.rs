@@ -0,0 +1,220 @@
+use std::hint::black_box;
+use std::time::Instant;
+use crate::clipping::{ClipSide, ClippingRectangle, cohen_sutherland_line_clip, cyrus_beck_line_clip, liang_barsky_line_clip,
+                      nicholl_lee_nicholl_line_clip, polygon_line_clip, sutherland_hodgman_polygon_clip, weiler_atherton_polygon_clip};
+use crate::draw::Draw;
+use crate::line::Line;
+use crate::line_style::LineStyle;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::golden_tests::assert_golden;
+
+const TOLERANCE: f32 = 1e-3;
+
+type LineClipper = fn(&Line, &ClippingRectangle) -> Option<Line>;
+
+fn window() -> ClippingRectangle {
+    ClippingRectangle::new(12.0, 12.0, 50.0, 50.0)
+}
+
+// Hand picked lines against window(): inside, outside, crossing, touching corners and edges, degenerate
+fn edge_case_lines() -> Vec<Line> {
+    vec![
+        Line::new(20.0, 20.0, 40.0, 30.0),
+        Line::new(2.0, 2.0, 62.0, 62.0),
+        Line::new(62.0, 62.0, 2.0, 2.0),
+        Line::new(2.0, 30.0, 60.0, 40.0),
+        Line::new(20.0, 2.0, 40.0, 62.0),
+        Line::new(2.0, 55.0, 60.0, 58.0),
+        Line::new(55.0, 2.0, 58.0, 60.0),
+        Line::new(0.0, 24.0, 24.0, 0.0),
+        Line::new(0.0, 30.0, 30.0, 0.0),
+        Line::new(12.0, 0.0, 12.0, 64.0),
+        Line::new(50.0, 20.0, 50.0, 40.0),
+        Line::new(0.0, 50.0, 64.0, 50.0),
+        Line::new(0.0, 11.0, 64.0, 11.0),
+        Line::new(20.0, 0.0, 20.0, 64.0),
+        Line::new(2.0, 62.0, 62.0, 2.0),
+        Line::new(12.0, 62.0, 62.0, 12.0),
+        Line::new(30.0, 30.0, 30.0, 30.0),
+        Line::new(5.0, 5.0, 5.0, 5.0),
+        Line::new(12.0, 12.0, 50.0, 50.0),
+        Line::new(-1000.0, 31.0, 1000.0, 29.0)
+    ]
+}
+
+// Deterministic pseudo random lines spread around the window
+fn random_lines(count: usize) -> Vec<Line> {
+    let mut state: u32 = 0x2545_f491;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state % 10_000) as f32 / 10_000.0 * 80.0 - 8.0
+    };
+    (0..count).map(|_| Line::new(next(), next(), next(), next())).collect()
+}
+
+fn assert_same_segment(line: &Line, expected: Option<Line>, actual: Option<Line>) {
+    let close = |a: f32, b: f32| (a - b).abs() <= TOLERANCE;
+    match (expected, actual) {
+        (None, None) => {}
+        (Some(expected), Some(actual)) => {
+            assert!(close(expected.first.x, actual.first.x) && close(expected.first.y, actual.first.y)
+                        && close(expected.last.x, actual.last.x) && close(expected.last.y, actual.last.y),
+                    "clipping {:?} -> {:?} gave {:?} -> {:?} instead of {:?} -> {:?}", line.first, line.last,
+                    actual.first, actual.last, expected.first, expected.last);
+        }
+        (expected, actual) => panic!("clipping {:?} -> {:?} gave {} instead of {}", line.first, line.last,
+                                     if actual.is_some() { "a segment" } else { "nothing" },
+                                     if expected.is_some() { "a segment" } else { "nothing" })
+    }
+}
+
+fn assert_matches_cohen_sutherland(clip: LineClipper) {
+    let rectangle = window();
+    for line in edge_case_lines().iter().chain(random_lines(10_000).iter()) {
+        assert_same_segment(line, cohen_sutherland_line_clip(line, &rectangle), clip(line, &rectangle));
+    }
+}
+
+#[test]
+fn liang_barsky_matches_cohen_sutherland() {
+    assert_matches_cohen_sutherland(liang_barsky_line_clip);
+}
+
+#[test]
+fn nicholl_lee_nicholl_matches_cohen_sutherland() {
+    assert_matches_cohen_sutherland(nicholl_lee_nicholl_line_clip);
+}
+
+// Run with cargo test line_clipping_benchmark --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn line_clipping_benchmark() {
+    let rectangle = window();
+    let lines = random_lines(100_000);
+    let clippers: [(&str, LineClipper); 3] = [("cohen-sutherland", cohen_sutherland_line_clip),
+                                              ("liang-barsky", liang_barsky_line_clip),
+                                              ("nicholl-lee-nicholl", nicholl_lee_nicholl_line_clip)];
+    for (name, clip) in clippers.iter() {
+        let start = Instant::now();
+        let mut accepted = 0;
+        for _ in 0..10 {
+            for line in lines.iter() {
+                if black_box(clip(black_box(line), &rectangle)).is_some() {
+                    accepted += 1;
+                }
+            }
+        }
+        let elapsed = start.elapsed();
+        println!("{}: {:.1} ns per line, {} accepted", name, elapsed.as_nanos() as f64 / (lines.len() * 10) as f64, accepted);
+    }
+}
+
+pub(crate) fn clipping_square() -> Vec<Point2d> {
+    vec![Point2d::new(12.0, 12.0), Point2d::new(12.0, 50.0),
+         Point2d::new(50.0, 50.0), Point2d::new(50.0, 12.0)]
+}
+
+#[test]
+fn cohen_sutherland_clipped_lines() {
+    let rectangle = ClippingRectangle::new(12.0, 12.0, 50.0, 50.0);
+    let lines = [Line::new(2.0, 2.0, 62.0, 62.0), Line::new(2.0, 30.0, 60.0, 40.0),
+                 Line::new(20.0, 2.0, 40.0, 62.0), Line::new(2.0, 55.0, 60.0, 58.0)];
+    let drawables: Vec<Box<dyn Draw>> = lines.iter()
+        .filter_map(|line| cohen_sutherland_line_clip(line, &rectangle))
+        .map(|line| Box::new(line) as Box<dyn Draw>)
+        .collect();
+    assert_golden("cohen_sutherland_clipped_lines", drawables);
+}
+
+#[test]
+fn cyrus_beck_clipped_lines() {
+    let lines = [Line::new(2.0, 2.0, 62.0, 62.0), Line::new(2.0, 30.0, 60.0, 40.0),
+                 Line::new(20.0, 2.0, 40.0, 62.0)];
+    let drawables: Vec<Box<dyn Draw>> = lines.iter()
+        .filter_map(|line| cyrus_beck_line_clip(line, &clipping_square()))
+        .map(|line| Box::new(line) as Box<dyn Draw>)
+        .collect();
+    assert_golden("cyrus_beck_clipped_lines", drawables);
+}
+
+#[test]
+fn sutherland_hodgman_clipped_polygon() {
+    let polygon = Polygon::new(PixelColor::red(), vec![Point2d::new(20.0, 2.0), Point2d::new(3.0, 35.0),
+                                                       Point2d::new(22.0, 62.0), Point2d::new(62.0, 36.0)]);
+    let clipped = sutherland_hodgman_polygon_clip(&polygon, &clipping_square()).unwrap();
+    assert_golden("sutherland_hodgman_clipped_polygon", vec![Box::new(clipped)]);
+}
+
+fn comb() -> Vec<Point2d> {
+    [(4.0, 4.0), (60.0, 4.0), (60.0, 58.0), (52.0, 58.0), (52.0, 14.0), (44.0, 14.0), (44.0, 58.0), (36.0, 58.0),
+     (36.0, 14.0), (28.0, 14.0), (28.0, 58.0), (20.0, 58.0), (20.0, 14.0), (12.0, 14.0), (12.0, 58.0), (4.0, 58.0)]
+        .iter().map(|(x, y)| Point2d::new(*x, *y)).collect()
+}
+
+fn horseshoe() -> Vec<Point2d> {
+    [(2.0, 20.0), (2.0, 50.0), (10.0, 50.0), (10.0, 30.0), (54.0, 30.0), (54.0, 50.0), (62.0, 50.0), (62.0, 20.0)]
+        .iter().map(|(x, y)| Point2d::new(*x, *y)).collect()
+}
+
+#[test]
+fn weiler_atherton_clipped_polygons() {
+    let colors = [PixelColor::red(), PixelColor::rgb(0, 255, 0), PixelColor::rgb(0, 0, 255), PixelColor::rgb(255, 255, 0)];
+    let pieces = weiler_atherton_polygon_clip(&Polygon::new(PixelColor::red(), comb()), &horseshoe());
+    assert_eq!(pieces.len(), 4);
+    let mut drawables: Vec<Box<dyn Draw>> = pieces.iter().zip(colors.iter())
+        .map(|(piece, color)| Box::new(Polygon { color: *color, ..piece.clone() }) as Box<dyn Draw>)
+        .collect();
+    drawables.push(Box::new(Polyline::closed(PixelColor::rgb(255, 255, 255), horseshoe())
+        .with_style(LineStyle::dashed(&[2.0, 2.0], 0.0).unwrap())));
+    assert_golden("weiler_atherton_clipped_polygons", drawables);
+    // Outlines sharing edges and vertices still give a single piece of the overlapping area
+    let square = Polygon::new(PixelColor::red(), vec![Point2d::new(10.0, 10.0), Point2d::new(30.0, 10.0),
+                                                      Point2d::new(30.0, 30.0), Point2d::new(10.0, 30.0)]);
+    let touching = weiler_atherton_polygon_clip(&square, &[Point2d::new(20.0, 10.0), Point2d::new(40.0, 10.0),
+                                                           Point2d::new(40.0, 30.0), Point2d::new(20.0, 30.0)]);
+    assert_eq!(touching.len(), 1);
+    assert!(touching[0].points.iter().all(|p| (p.x == 20.0 || p.x == 30.0) && (p.y == 10.0 || p.y == 30.0)),
+            "unexpected piece {:?}", touching[0].points);
+    // Clip region sharing only an edge with the polygon leaves nothing of it
+    let adjacent = weiler_atherton_polygon_clip(&square, &[Point2d::new(30.0, 10.0), Point2d::new(40.0, 10.0),
+                                                           Point2d::new(40.0, 30.0), Point2d::new(30.0, 30.0)]);
+    assert!(adjacent.is_empty(), "unexpected pieces {:?}", adjacent.iter().map(|piece| &piece.points).collect::<Vec<_>>());
+    // Without crossings the result is the inner outline or nothing
+    let gap = Polygon::new(PixelColor::red(), vec![Point2d::new(14.0, 20.0), Point2d::new(18.0, 20.0),
+                                                   Point2d::new(18.0, 40.0), Point2d::new(14.0, 40.0)]);
+    assert_eq!(weiler_atherton_polygon_clip(&gap, &comb()).len(), 0);
+    assert_eq!(weiler_atherton_polygon_clip(&Polygon::new(PixelColor::red(), comb()), &gap.points).len(), 0);
+    let inner = Polygon::new(PixelColor::red(), vec![Point2d::new(5.0, 5.0), Point2d::new(11.0, 5.0), Point2d::new(8.0, 50.0)]);
+    assert_eq!(weiler_atherton_polygon_clip(&inner, &comb())[0].points, inner.points);
+}
+
+#[test]
+fn polygon_clipped_lines() {
+    let window = Polygon::new(PixelColor::rgb(96, 96, 96), comb())
+        .with_holes(vec![vec![Point2d::new(20.0, 6.0), Point2d::new(20.0, 11.0), Point2d::new(44.0, 11.0), Point2d::new(44.0, 6.0)]]);
+    let lines = [Line::new(0.0, 8.5, 64.0, 8.5), Line::new(0.0, 30.0, 64.0, 50.0), Line::new(8.0, 62.0, 56.0, 2.0),
+                 Line::new(6.0, 20.0, 10.0, 40.0)];
+    let mut drawables: Vec<Box<dyn Draw>> = vec![Box::new(window.outline())];
+    for line in lines.iter() {
+        let inside = polygon_line_clip(line, &window, ClipSide::Inside);
+        let outside = polygon_line_clip(line, &window, ClipSide::Outside);
+        let length = |lines: &[Line]| lines.iter().map(|l| ((l.last.x - l.first.x).powi(2) + (l.last.y - l.first.y).powi(2)).sqrt()).sum::<f32>();
+        assert!((length(&inside) + length(&outside) - length(std::slice::from_ref(line))).abs() < 0.01);
+        drawables.extend(inside.into_iter().map(|l| Box::new(Line { color: PixelColor::red(), ..l }) as Box<dyn Draw>));
+        drawables.extend(outside.into_iter().map(|l| Box::new(Line { color: PixelColor::rgb(0, 0, 255), ..l }) as Box<dyn Draw>));
+    }
+    assert_golden("polygon_clipped_lines", drawables);
+    // Crossing every tooth of the comb gives one piece per tooth, the hole splits the base
+    assert_eq!(polygon_line_clip(&Line::new(0.0, 30.0, 64.0, 30.0), &window, ClipSide::Inside).len(), 4);
+    assert_eq!(polygon_line_clip(&Line::new(0.0, 8.5, 64.0, 8.5), &window, ClipSide::Inside).len(), 2);
+    assert_eq!(polygon_line_clip(&Line::new(0.0, 8.5, 64.0, 8.5), &window, ClipSide::Outside).len(), 3);
+    // Passing through a vertex between two inside parts gives a single piece
+    let through_vertex = polygon_line_clip(&Line::new(16.0, 10.0, 24.0, 18.0), &window, ClipSide::Inside);
+    assert_eq!(through_vertex.len(), 1);
+    assert_eq!((through_vertex[0].first, through_vertex[0].last), (Point2d::new(16.0, 10.0), Point2d::new(24.0, 18.0)));
+}
@@ -0,0 +1,118 @@
+use crate::pixel_color::PixelColor;
+
+// Porter-Duff operators
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CompositeOperator {
+    Clear,
+    Source,
+    Destination,
+    SourceOver,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor
+}
+
+impl CompositeOperator {
+    // Fractions of source and destination contributing to the result
+    fn factors(&self, source_alpha: f32, destination_alpha: f32) -> (f32, f32) {
+        match self {
+            CompositeOperator::Clear => (0.0, 0.0),
+            CompositeOperator::Source => (1.0, 0.0),
+            CompositeOperator::Destination => (0.0, 1.0),
+            CompositeOperator::SourceOver => (1.0, 1.0 - source_alpha),
+            CompositeOperator::DestinationOver => (1.0 - destination_alpha, 1.0),
+            CompositeOperator::SourceIn => (destination_alpha, 0.0),
+            CompositeOperator::DestinationIn => (0.0, source_alpha),
+            CompositeOperator::SourceOut => (1.0 - destination_alpha, 0.0),
+            CompositeOperator::DestinationOut => (0.0, 1.0 - source_alpha),
+            CompositeOperator::SourceAtop => (destination_alpha, 1.0 - source_alpha),
+            CompositeOperator::DestinationAtop => (1.0 - destination_alpha, source_alpha),
+            CompositeOperator::Xor => (1.0 - destination_alpha, 1.0 - source_alpha)
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    Difference
+}
+
+impl BlendMode {
+    fn blend(&self, backdrop: f32, source: f32) -> f32 {
+        match self {
+            BlendMode::Normal => source,
+            BlendMode::Multiply => backdrop * source,
+            BlendMode::Screen => backdrop + source - backdrop * source,
+            BlendMode::Overlay => {
+                if backdrop <= 0.5 {
+                    source * 2.0 * backdrop
+                } else {
+                    let doubled = 2.0 * backdrop - 1.0;
+                    source + doubled - source * doubled
+                }
+            }
+            BlendMode::Darken => backdrop.min(source),
+            BlendMode::Lighten => backdrop.max(source),
+            BlendMode::Difference => (backdrop - source).abs()
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Compositing {
+    pub operator: CompositeOperator,
+    pub blend_mode: BlendMode
+}
+
+impl Compositing {
+    pub fn new(operator: CompositeOperator, blend_mode: BlendMode) -> Compositing {
+        Compositing { operator, blend_mode }
+    }
+
+    pub fn source_over() -> Compositing {
+        Compositing::new(CompositeOperator::SourceOver, BlendMode::Normal)
+    }
+
+    // Overwriting the destination gives the same result, so the pixel does not have to be read
+    pub fn is_opaque_copy(&self, color: &PixelColor, coverage: f32) -> bool {
+        coverage >= 1.0 && self.blend_mode == BlendMode::Normal && (self.operator == CompositeOperator::Source ||
+            (self.operator == CompositeOperator::SourceOver && color.alpha == 255))
+    }
+
+    // The blend mode mixes source with the backdrop first, the result is then combined using the operator.
+    // Coverage interpolates between the untouched destination and the composited color.
+    pub fn composite(&self, source: &PixelColor, destination: &PixelColor, coverage: f32) -> PixelColor {
+        let source_alpha = source.alpha as f32 / 255.0;
+        let destination_alpha = destination.alpha as f32 / 255.0;
+        let (source_factor, destination_factor) = self.operator.factors(source_alpha, destination_alpha);
+        let coverage = coverage.clamp(0.0, 1.0);
+        let result_alpha = source_alpha * source_factor + destination_alpha * destination_factor;
+        let channel = |source_channel: u8, destination_channel: u8| -> f32 {
+            let source_value = source_channel as f32 / 255.0;
+            let destination_value = destination_channel as f32 / 255.0;
+            let mixed = (1.0 - destination_alpha) * source_value +
+                destination_alpha * self.blend_mode.blend(destination_value, source_value);
+            let premultiplied = source_alpha * source_factor * mixed + destination_alpha * destination_factor * destination_value;
+            let value = if result_alpha > 0.0 { premultiplied / result_alpha } else { 0.0 };
+            value + (destination_value - value) * (1.0 - coverage)
+        };
+        let to_byte = |value: f32| -> u8 { (value.clamp(0.0, 1.0) * 255.0).round() as u8 };
+        PixelColor {
+            red: to_byte(channel(source.red, destination.red)),
+            green: to_byte(channel(source.green, destination.green)),
+            blue: to_byte(channel(source.blue, destination.blue)),
+            alpha: to_byte(result_alpha + (destination_alpha - result_alpha) * (1.0 - coverage))
+        }
+    }
+}
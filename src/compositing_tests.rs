@@ -0,0 +1,28 @@
+use crate::compositing::{BlendMode, CompositeOperator, Compositing};
+use crate::draw::Draw;
+use crate::line::Line;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+use crate::golden_tests::assert_golden;
+
+#[test]
+fn compositing_overlays() {
+    let square = |x: f32, y: f32, color: PixelColor, compositing: Compositing| -> Box<dyn Draw> {
+        Box::new(Polygon::new(color, vec![Point2d::new(x, y), Point2d::new(x, y + 20.0),
+                                          Point2d::new(x + 20.0, y + 20.0), Point2d::new(x + 20.0, y)])
+            .with_compositing(compositing))
+    };
+    let half_blue = PixelColor::rgb(0, 0, 255).with_alpha(128);
+    assert_golden("compositing_overlays", vec![
+        Box::new(Polygon::new(PixelColor::rgb(200, 100, 50), vec![Point2d::new(0.0, 0.0), Point2d::new(0.0, 64.0),
+                                                                  Point2d::new(64.0, 64.0), Point2d::new(64.0, 0.0)])),
+        square(4.0, 4.0, half_blue, Compositing::source_over()),
+        square(36.0, 4.0, PixelColor::rgb(128, 128, 128), Compositing::new(CompositeOperator::SourceOver, BlendMode::Multiply)),
+        square(4.0, 36.0, PixelColor::rgb(128, 128, 128), Compositing::new(CompositeOperator::SourceOver, BlendMode::Screen)),
+        square(36.0, 36.0, half_blue, Compositing::new(CompositeOperator::DestinationOut, BlendMode::Normal)),
+        square(20.0, 20.0, PixelColor::rgb(60, 200, 60), Compositing::new(CompositeOperator::SourceAtop, BlendMode::Overlay)),
+        Box::new(Line::with_color(half_blue, 2.0, 62.0, 62.0, 2.0)
+            .with_compositing(Compositing::new(CompositeOperator::Xor, BlendMode::Normal)))
+    ]);
+}
@@ -1,5 +1,5 @@
-use crate::raw_canvas::RawCanvas;
+use crate::canvas::Canvas;
 
 pub trait Draw {
-    fn draw(&self, canvas: &RawCanvas);
+    fn draw(&self, canvas: &mut dyn Canvas);
 }
\ No newline at end of file
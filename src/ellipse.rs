@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::f32::consts::FRAC_PI_2;
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+
+// The midpoint algorithm walks every row of the ellipse, larger ones are sampled only in the rows of the canvas
+const MIDPOINT_RADIUS_LIMIT: f32 = 65536.0;
+
+#[derive(Copy, Clone)]
+pub struct Ellipse {
+    pub center: Point2d,
+    pub radius_x: f32,
+    pub radius_y: f32,
+    // Counter clockwise angle in radians between the x axis and the radius_x axis
+    pub rotation: f32,
+    pub color: PixelColor,
+    pub filled: bool,
+    pub compositing: Compositing
+}
+
+impl Ellipse {
+    pub fn new(color: PixelColor, center: Point2d, radius_x: f32, radius_y: f32) -> Ellipse {
+        Ellipse { center, radius_x, radius_y, rotation: 0.0, color, filled: false, compositing: Compositing::source_over() }
+    }
+
+    pub fn filled(color: PixelColor, center: Point2d, radius_x: f32, radius_y: f32) -> Ellipse {
+        Ellipse { filled: true, ..Ellipse::new(color, center, radius_x, radius_y) }
+    }
+
+    pub fn with_rotation(&self, rotation: f32) -> Ellipse {
+        Ellipse { rotation, ..*self }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Ellipse {
+        Ellipse { compositing, ..*self }
+    }
+
+    // Radii for the midpoint algorithm, rotations by multiples of right angle only swap them
+    fn midpoint_radii(&self) -> Option<(f32, f32)> {
+        let quarters = self.rotation / FRAC_PI_2;
+        if (quarters - quarters.round()).abs() > 1e-4 || self.radius_x.max(self.radius_y) > MIDPOINT_RADIUS_LIMIT {
+            None
+        } else if (quarters.round() as i64).rem_euclid(2) == 0 {
+            Some((self.radius_x, self.radius_y))
+        } else {
+            Some((self.radius_y, self.radius_x))
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.center.x.is_finite() && self.center.y.is_finite() && self.rotation.is_finite() &&
+            self.radius_x.is_finite() && self.radius_y.is_finite() && self.radius_x >= 0.0 && self.radius_y >= 0.0
+    }
+
+    // Spans covered by the ellipse as row -> (left, right), inclusive. Sampled spans are limited to the rows
+    // from -1 to height, so the outline can still check the neighbours of the visible rows.
+    fn spans(&self, height: i32) -> BTreeMap<i32, (i32, i32)> {
+        match self.midpoint_radii() {
+            Some((radius_x, radius_y)) => {
+                let center_x = self.center.x.floor() as i32;
+                let center_y = self.center.y.floor() as i32;
+                let mut spans = BTreeMap::new();
+                for (x, y) in midpoint_quadrant(radius_x, radius_y).iter() {
+                    for row in [center_y + y, center_y - y].iter() {
+                        let span = spans.entry(*row).or_insert((center_x, center_x));
+                        span.0 = span.0.min(center_x - x);
+                        span.1 = span.1.max(center_x + x);
+                    }
+                }
+                spans
+            }
+            None => self.sampled_spans(height)
+        }
+    }
+
+    // Ellipse is sampled at pixel centers using its implicit equation A x^2 + B x y + C y^2 = 1,
+    // in double precision so that huge radii do not underflow the coefficients
+    fn sampled_spans(&self, height: i32) -> BTreeMap<i32, (i32, i32)> {
+        let mut spans = BTreeMap::new();
+        let radius_x = (self.radius_x as f64).max(1e-3);
+        let radius_y = (self.radius_y as f64).max(1e-3);
+        let (center_x, center_y) = (self.center.x as f64, self.center.y as f64);
+        let (sin, cos) = (self.rotation as f64).sin_cos();
+        let a = cos * cos / (radius_x * radius_x) + sin * sin / (radius_y * radius_y);
+        let b = 2.0 * cos * sin * (1.0 / (radius_x * radius_x) - 1.0 / (radius_y * radius_y));
+        let c = sin * sin / (radius_x * radius_x) + cos * cos / (radius_y * radius_y);
+        let half_height = (radius_x * radius_x * sin * sin + radius_y * radius_y * cos * cos).sqrt();
+        let bottom = ((center_y - half_height).floor().max(-1.0)) as i32;
+        let top = ((center_y + half_height).ceil().min(height as f64)) as i32;
+        for row in bottom..(top + 1) {
+            let y = row as f64 + 0.5 - center_y;
+            let linear = b * y;
+            let constant = c * y * y - 1.0;
+            let discriminant = linear * linear - 4.0 * a * constant;
+            if discriminant < 0.0 {
+                continue;
+            }
+            let root = discriminant.sqrt();
+            let left = ((-linear - root) / (2.0 * a) + center_x - 0.5).ceil() as i32;
+            let right = ((-linear + root) / (2.0 * a) + center_x - 0.5).floor() as i32;
+            if left <= right {
+                spans.insert(row, (left, right));
+            }
+        }
+        spans
+    }
+}
+
+// Midpoint ellipse algorithm, points of the first quadrant relative to the center
+fn midpoint_quadrant(radius_x: f32, radius_y: f32) -> Vec<(i32, i32)> {
+    let a = radius_x.round() as i64;
+    let b = radius_y.round() as i64;
+    let mut result = Vec::new();
+    if b == 0 {
+        for x in 0..(a + 1) {
+            result.push((x as i32, 0));
+        }
+        return result;
+    }
+    let (a2, b2) = ((a * a) as f64, (b * b) as f64);
+    let mut x: i64 = 0;
+    let mut y: i64 = b;
+    let mut delta_x = 0.0;
+    let mut delta_y = 2.0 * a2 * y as f64;
+    let mut decision = b2 - a2 * b as f64 + a2 / 4.0;
+    while delta_x < delta_y {
+        result.push((x as i32, y as i32));
+        x += 1;
+        delta_x += 2.0 * b2;
+        if decision < 0.0 {
+            decision += delta_x + b2;
+        } else {
+            y -= 1;
+            delta_y -= 2.0 * a2;
+            decision += delta_x - delta_y + b2;
+        }
+    }
+    decision = b2 * (x as f64 + 0.5).powi(2) + a2 * ((y - 1) as f64).powi(2) - a2 * b2;
+    while y >= 0 {
+        result.push((x as i32, y as i32));
+        y -= 1;
+        delta_y -= 2.0 * a2;
+        if decision > 0.0 {
+            decision += a2 - delta_y;
+        } else {
+            x += 1;
+            delta_x += 2.0 * b2;
+            decision += delta_x - delta_y + a2;
+        }
+    }
+    result
+}
+
+impl Draw for Ellipse {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        if !self.is_valid() {
+            return;
+        }
+        // Ellipses away from the canvas are skipped, which also keeps the pixel coordinates of the midpoint path in range
+        let reach = self.radius_x.max(self.radius_y) + 1.0;
+        if self.center.x + reach < 0.0 || self.center.y + reach < 0.0 ||
+            self.center.x - reach > canvas.width() as f32 || self.center.y - reach > canvas.height() as f32 {
+            return;
+        }
+        if !self.filled {
+            if let Some((radius_x, radius_y)) = self.midpoint_radii() {
+                let center_x = self.center.x.floor() as i32;
+                let center_y = self.center.y.floor() as i32;
+                let mut points: Vec<(i32, i32)> = midpoint_quadrant(radius_x, radius_y).iter()
+                    .flat_map(|(x, y)| vec![(*x, *y), (-*x, *y), (*x, -*y), (-*x, -*y)])
+                    .collect();
+                points.sort_unstable();
+                points.dedup();
+                for (x, y) in points.iter() {
+                    canvas.composite_pixel(center_x + x, center_y + y, &self.color, 1.0, &self.compositing);
+                }
+                return;
+            }
+        }
+        let spans = self.spans(canvas.height() as i32);
+        if self.filled {
+            for (row, (left, right)) in spans.iter() {
+                canvas.fill_span(*row, *left, *right, &self.color, &self.compositing);
+            }
+        } else {
+            // Outline of sampled ellipse is made of the pixels which have a 4-connected neighbour outside of it
+            let inside = |row: i32, x: i32| spans.get(&row).is_some_and(|(left, right)| *left <= x && x <= *right);
+            let width = canvas.width() as i32;
+            for (row, (left, right)) in spans.iter() {
+                for x in (*left).max(-1)..((*right).min(width) + 1) {
+                    if x == *left || x == *right || !inside(row + 1, x) || !inside(row - 1, x) {
+                        canvas.composite_pixel(x, *row, &self.color, 1.0, &self.compositing);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Connectivity {
+    Four,
+    Eight
+}
+
+#[derive(Copy, Clone)]
+pub struct FloodFill {
+    pub seed: Point2d,
+    pub color: PixelColor,
+    pub connectivity: Connectivity,
+    // Region is bounded by pixels of this color instead of being the pixels matching the seed color
+    pub boundary: Option<PixelColor>,
+    pub compositing: Compositing
+}
+
+impl FloodFill {
+    pub fn new(color: PixelColor, seed: Point2d) -> FloodFill {
+        FloodFill { seed, color, connectivity: Connectivity::Four, boundary: None,
+                    compositing: Compositing::source_over() }
+    }
+
+    pub fn boundary(color: PixelColor, seed: Point2d, boundary: PixelColor) -> FloodFill {
+        FloodFill { boundary: Some(boundary), ..FloodFill::new(color, seed) }
+    }
+
+    pub fn with_connectivity(&self, connectivity: Connectivity) -> FloodFill {
+        FloodFill { connectivity, ..*self }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> FloodFill {
+        FloodFill { compositing, ..*self }
+    }
+
+    // Scanline seed fill, returns number of filled pixels. Visited pixels are tracked separately,
+    // so the fill stops even when the painted color reads back as part of the region
+    pub fn fill(&self, canvas: &mut dyn Canvas) -> usize {
+        let (seed_x, seed_y) = (self.seed.x.floor() as i32, self.seed.y.floor() as i32);
+        let target = match canvas.get_pixel(seed_x, seed_y) {
+            Some(target) => target,
+            None => return 0
+        };
+        let (width, height) = (canvas.width() as i32, canvas.height() as i32);
+        let mut visited = vec![false; (width * height) as usize];
+        let inside = |canvas: &dyn Canvas, visited: &[bool], x: i32, y: i32| -> bool {
+            if !canvas.contains(x, y) || visited[(y * width + x) as usize] {
+                return false;
+            }
+            let pixel = canvas.get_pixel(x, y).unwrap();
+            match self.boundary {
+                Some(boundary) => pixel != boundary,
+                None => pixel == target
+            }
+        };
+        // Diagonal neighbours are reached by scanning one pixel further on the adjacent rows
+        let reach = match self.connectivity {
+            Connectivity::Four => 0,
+            Connectivity::Eight => 1
+        };
+        let mut filled = 0;
+        let mut stack = vec![(seed_x, seed_y)];
+        while let Some((x, y)) = stack.pop() {
+            if !inside(canvas, &visited, x, y) {
+                continue;
+            }
+            let mut left = x;
+            while inside(canvas, &visited, left - 1, y) {
+                left -= 1;
+            }
+            let mut right = x;
+            while inside(canvas, &visited, right + 1, y) {
+                right += 1;
+            }
+            for i in left..=right {
+                visited[(y * width + i) as usize] = true;
+            }
+            canvas.fill_span(y, left, right, &self.color, &self.compositing);
+            filled += (right - left + 1) as usize;
+            for row in [y - 1, y + 1].iter().filter(|row| **row >= 0 && **row < height) {
+                let mut in_run = false;
+                for i in (left - reach)..=(right + reach) {
+                    let is_inside = inside(canvas, &visited, i, *row);
+                    if is_inside && !in_run {
+                        stack.push((i, *row));
+                    }
+                    in_run = is_inside;
+                }
+            }
+        }
+        filled
+    }
+}
+
+impl Draw for FloodFill {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        self.fill(canvas);
+    }
+}
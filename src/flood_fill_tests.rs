@@ -0,0 +1,42 @@
+use crate::circle::Circle;
+use crate::flood_fill::{Connectivity, FloodFill};
+use crate::line::Line;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polyline::Polyline;
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden, render};
+
+#[test]
+fn flood_fills() {
+    let white = PixelColor::rgb(255, 255, 255);
+    let square = Polyline::closed(white, vec![Point2d::new(4.0, 4.0), Point2d::new(27.0, 4.0), Point2d::new(27.0, 27.0),
+                                              Point2d::new(4.0, 27.0)]);
+    let diamond = Polyline::closed(white, vec![Point2d::new(48.0, 3.0), Point2d::new(61.0, 16.0), Point2d::new(48.0, 29.0),
+                                               Point2d::new(35.0, 16.0)]);
+    let ring = Circle::new(PixelColor::rgb(0, 255, 0), Point2d::new(16.0, 48.0), 12.0);
+    let chord = Line::with_color(PixelColor::rgb(0, 0, 255), 8.0, 44.0, 24.0, 52.0);
+    let stairs = Line::with_color(PixelColor::rgb(255, 255, 0), 36.0, 36.0, 60.0, 60.0);
+    let stairs_copy = Line::with_color(PixelColor::rgb(255, 255, 0), 44.0, 36.0, 62.0, 54.0);
+    assert_golden("flood_fills", vec![
+        Box::new(square.clone()),
+        Box::new(FloodFill::new(PixelColor::red(), Point2d::new(15.0, 15.0))),
+        Box::new(diamond.clone()),
+        Box::new(FloodFill::new(PixelColor::rgb(255, 0, 255), Point2d::new(48.0, 16.0))),
+        Box::new(ring),
+        Box::new(chord),
+        Box::new(FloodFill::boundary(PixelColor::rgb(0, 255, 255), Point2d::new(16.0, 40.0), PixelColor::rgb(0, 255, 0))),
+        Box::new(stairs),
+        Box::new(stairs_copy),
+        Box::new(FloodFill::new(PixelColor::red(), Point2d::new(36.0, 36.0)).with_connectivity(Connectivity::Eight)),
+        Box::new(FloodFill::new(PixelColor::red(), Point2d::new(44.0, 36.0)))
+    ]);
+    // Closed outline keeps the fill inside, diagonal steps of the lines let eight connected fill leak out
+    let mut canvas = render(&[Box::new(square)]);
+    assert_eq!(FloodFill::new(PixelColor::red(), Point2d::new(15.0, 15.0)).fill(&mut canvas), 22 * 22);
+    let mut canvas = render(&[Box::new(diamond.clone())]);
+    let inside = FloodFill::new(PixelColor::red(), Point2d::new(48.0, 16.0)).fill(&mut canvas);
+    let mut canvas = render(&[Box::new(diamond)]);
+    let leaked = FloodFill::new(PixelColor::red(), Point2d::new(48.0, 16.0)).with_connectivity(Connectivity::Eight)
+        .fill(&mut canvas);
+    assert!(inside < 13 * 13 * 2 && leaked > (WIDTH * HEIGHT) as usize / 2, "inside {}, leaked {}", inside, leaked);
+}
@@ -0,0 +1,119 @@
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use crate::canvas::Canvas;
+use crate::draw::Draw;
+use crate::image_export::write_ppm;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+
+// Set IBUKI_UPDATE_GOLDEN=1 to overwrite the reference images with the current output
+const UPDATE_VARIABLE: &str = "IBUKI_UPDATE_GOLDEN";
+pub(crate) const WIDTH: u32 = 64;
+pub(crate) const HEIGHT: u32 = 64;
+
+struct RgbImage {
+    width: u32,
+    height: u32,
+    data: Vec<u8>
+}
+
+impl RgbImage {
+    fn from_canvas(canvas: &dyn Canvas) -> RgbImage {
+        let mut data = Vec::new();
+        write_ppm(canvas, &mut data).unwrap();
+        RgbImage::from_ppm(&data).unwrap()
+    }
+
+    fn from_ppm(bytes: &[u8]) -> Option<RgbImage> {
+        let mut fields: Vec<u32> = Vec::new();
+        let mut position = 2;
+        if bytes.get(0..2)? != b"P6" {
+            return None;
+        }
+        while fields.len() < 3 {
+            while bytes.get(position)?.is_ascii_whitespace() {
+                position += 1;
+            }
+            let begin = position;
+            while bytes.get(position)?.is_ascii_digit() {
+                position += 1;
+            }
+            fields.push(std::str::from_utf8(&bytes[begin..position]).ok()?.parse().ok()?);
+        }
+        let data = bytes.get((position + 1)..)?.to_vec();
+        if fields[2] != 255 || data.len() != (fields[0] * fields[1] * 3) as usize {
+            return None;
+        }
+        Some(RgbImage { width: fields[0], height: fields[1], data })
+    }
+
+    fn write_ppm(&self, path: &PathBuf) {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend_from_slice(&self.data);
+        fs::write(path, bytes).unwrap();
+    }
+}
+
+fn golden_directory() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn diff_directory() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target").join("golden-diff")
+}
+
+// Mismatching pixels are painted red, matching ones are kept as a dimmed copy of the reference
+fn diff_image(expected: &RgbImage, actual: &RgbImage) -> (RgbImage, usize) {
+    let mut data = Vec::with_capacity(expected.data.len());
+    let mut mismatches = 0;
+    for (expected_pixel, actual_pixel) in expected.data.chunks(3).zip(actual.data.chunks(3)) {
+        if expected_pixel == actual_pixel {
+            data.extend(expected_pixel.iter().map(|c| c / 4));
+        } else {
+            mismatches += 1;
+            data.extend_from_slice(&[255, 0, 0]);
+        }
+    }
+    (RgbImage { width: expected.width, height: expected.height, data }, mismatches)
+}
+
+pub(crate) fn render(drawables: &[Box<dyn Draw>]) -> MemoryCanvas {
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    canvas.clean_color(&PixelColor::black());
+    for drawable in drawables.iter() {
+        drawable.draw(&mut canvas);
+    }
+    canvas
+}
+
+pub(crate) fn assert_golden(name: &str, drawables: Vec<Box<dyn Draw>>) {
+    assert_golden_canvas(name, &render(&drawables));
+}
+
+pub(crate) fn assert_golden_canvas(name: &str, canvas: &MemoryCanvas) {
+    let actual = RgbImage::from_canvas(canvas);
+    let reference_path = golden_directory().join(format!("{}.ppm", name));
+    if env::var(UPDATE_VARIABLE).is_ok() {
+        fs::create_dir_all(golden_directory()).unwrap();
+        let mut writer = BufWriter::new(File::create(&reference_path).unwrap());
+        write_ppm(canvas, &mut writer).unwrap();
+        return;
+    }
+    let bytes = fs::read(&reference_path)
+        .unwrap_or_else(|_| panic!("Missing reference image {}, run with {}=1", reference_path.display(), UPDATE_VARIABLE));
+    let expected = RgbImage::from_ppm(&bytes)
+        .unwrap_or_else(|| panic!("Malformed reference image {}", reference_path.display()));
+    assert!(expected.width == actual.width && expected.height == actual.height,
+            "{}: expected {}x{} image, got {}x{}", name, expected.width, expected.height, actual.width, actual.height);
+    let (diff, mismatches) = diff_image(&expected, &actual);
+    if mismatches > 0 {
+        fs::create_dir_all(diff_directory()).unwrap();
+        let diff_path = diff_directory().join(format!("{}.diff.ppm", name));
+        diff.write_ppm(&diff_path);
+        actual.write_ppm(&diff_directory().join(format!("{}.actual.ppm", name)));
+        panic!("{}: {} pixels differ from the reference, see {}", name, mismatches, diff_path.display());
+    }
+}
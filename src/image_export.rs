@@ -0,0 +1,194 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::canvas::Canvas;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ImageFormat {
+    Ppm,
+    Bmp,
+    Png
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "bmp" => Some(ImageFormat::Bmp),
+            "png" => Some(ImageFormat::Png),
+            _ => None
+        }
+    }
+}
+
+pub fn save_image(canvas: &dyn Canvas, path: &Path) -> io::Result<()> {
+    let format = ImageFormat::from_path(path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("Unsupported image extension: {}", path.display()))
+    })?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_image(canvas, format, &mut writer)?;
+    writer.flush()
+}
+
+pub fn write_image(canvas: &dyn Canvas, format: ImageFormat, writer: &mut dyn Write) -> io::Result<()> {
+    match format {
+        ImageFormat::Ppm => write_ppm(canvas, writer),
+        ImageFormat::Bmp => write_bmp(canvas, writer),
+        ImageFormat::Png => write_png(canvas, writer)
+    }
+}
+
+// Canvas rows are stored top to bottom, so the first row is the one with the biggest y
+fn rgb_row(canvas: &dyn Canvas, row: u32) -> Vec<u8> {
+    let width = canvas.width() as usize;
+    let begin = row as usize * width;
+    let mut result = Vec::with_capacity(width * 3);
+    for value in canvas.pixels()[begin..(begin + width)].iter() {
+        let color = canvas.format().decode(*value);
+        result.push(color.red);
+        result.push(color.green);
+        result.push(color.blue);
+    }
+    result
+}
+
+pub fn write_ppm(canvas: &dyn Canvas, writer: &mut dyn Write) -> io::Result<()> {
+    write!(writer, "P6\n{} {}\n255\n", canvas.width(), canvas.height())?;
+    for row in 0..canvas.height() {
+        writer.write_all(&rgb_row(canvas, row))?;
+    }
+    Ok(())
+}
+
+const BMP_HEADER_SIZE: u32 = 14;
+const BMP_INFO_HEADER_SIZE: u32 = 40;
+
+pub fn write_bmp(canvas: &dyn Canvas, writer: &mut dyn Write) -> io::Result<()> {
+    let row_size = (canvas.width() * 3 + 3) & !3;
+    let image_size = row_size * canvas.height();
+    let data_offset = BMP_HEADER_SIZE + BMP_INFO_HEADER_SIZE;
+    writer.write_all(b"BM")?;
+    writer.write_all(&(data_offset + image_size).to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(&data_offset.to_le_bytes())?;
+    writer.write_all(&BMP_INFO_HEADER_SIZE.to_le_bytes())?;
+    writer.write_all(&(canvas.width() as i32).to_le_bytes())?;
+    writer.write_all(&(canvas.height() as i32).to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?;
+    writer.write_all(&24u16.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(&image_size.to_le_bytes())?;
+    writer.write_all(&2835i32.to_le_bytes())?;
+    writer.write_all(&2835i32.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    // BMP stores rows bottom-up, which matches the y axis used by put_pixel
+    for row in (0..canvas.height()).rev() {
+        let mut data = rgb_row(canvas, row);
+        for pixel in data.chunks_mut(3) {
+            pixel.swap(0, 2);
+        }
+        data.resize(row_size as usize, 0);
+        writer.write_all(&data)?;
+    }
+    Ok(())
+}
+
+const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+const MAX_STORED_BLOCK_SIZE: usize = 65535;
+
+pub fn write_png(canvas: &dyn Canvas, writer: &mut dyn Write) -> io::Result<()> {
+    writer.write_all(&PNG_SIGNATURE)?;
+    let mut header = Vec::new();
+    header.extend_from_slice(&canvas.width().to_be_bytes());
+    header.extend_from_slice(&canvas.height().to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_png_chunk(writer, b"IHDR", &header)?;
+    let mut raw = Vec::new();
+    for row in 0..canvas.height() {
+        raw.push(0);
+        raw.extend_from_slice(&rgb_row(canvas, row));
+    }
+    write_png_chunk(writer, b"IDAT", &zlib_stored(&raw))?;
+    write_png_chunk(writer, b"IEND", &[])
+}
+
+fn write_png_chunk(writer: &mut dyn Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    let mut crc = Crc32::new();
+    crc.update(kind);
+    crc.update(data);
+    writer.write_all(&crc.value().to_be_bytes())
+}
+
+// zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let mut chunks = data.chunks(MAX_STORED_BLOCK_SIZE).peekable();
+    if chunks.peek().is_none() {
+        result.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let last = if chunks.peek().is_none() { 1 } else { 0 };
+        let length = chunk.len() as u16;
+        result.push(last);
+        result.extend_from_slice(&length.to_le_bytes());
+        result.extend_from_slice(&(!length).to_le_bytes());
+        result.extend_from_slice(chunk);
+    }
+    result.extend_from_slice(&adler32(data).to_be_bytes());
+    result
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for byte in data.iter() {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            c = if c & 1 != 0 { 0xedb88320 ^ (c >> 1) } else { c >> 1 };
+            bit += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+// Built once at compile time instead of for every chunk
+const CRC_TABLE: [u32; 256] = crc_table();
+
+struct Crc32 {
+    crc: u32
+}
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        Crc32 { crc: 0xffffffff }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for byte in data.iter() {
+            self.crc = CRC_TABLE[((self.crc ^ *byte as u32) & 0xff) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn value(&self) -> u32 {
+        self.crc ^ 0xffffffff
+    }
+}
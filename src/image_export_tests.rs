@@ -0,0 +1,43 @@
+use crate::canvas::Canvas;
+use crate::image_export::{write_bmp, write_png};
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+
+fn bytes(write: fn(&dyn Canvas, &mut dyn std::io::Write) -> std::io::Result<()>, canvas: &MemoryCanvas) -> Vec<u8> {
+    let mut data = Vec::new();
+    write(canvas, &mut data).unwrap();
+    data
+}
+
+#[test]
+fn bmp_bytes() {
+    // Red and green on the bottom row, blue and white on the top one
+    let mut canvas = MemoryCanvas::new(2, 2);
+    canvas.put_pixel(0, 0, &PixelColor::red());
+    canvas.put_pixel(1, 0, &PixelColor::rgb(0, 255, 0));
+    canvas.put_pixel(0, 1, &PixelColor::rgb(0, 0, 255));
+    canvas.put_pixel(1, 1, &PixelColor::rgb(255, 255, 255));
+    let expected: Vec<u8> = [
+        &b"BM"[..], &[70, 0, 0, 0], &[0, 0, 0, 0], &[54, 0, 0, 0],
+        &[40, 0, 0, 0], &[2, 0, 0, 0], &[2, 0, 0, 0], &[1, 0], &[24, 0], &[0, 0, 0, 0], &[16, 0, 0, 0],
+        &[0x13, 0x0b, 0, 0], &[0x13, 0x0b, 0, 0], &[0, 0, 0, 0], &[0, 0, 0, 0],
+        // Rows bottom-up in BGR order, padded to four bytes
+        &[0, 0, 255, 0, 255, 0, 0, 0],
+        &[255, 0, 0, 255, 255, 255, 0, 0]
+    ].concat();
+    assert_eq!(bytes(write_bmp, &canvas), expected);
+}
+
+#[test]
+fn png_bytes() {
+    let mut canvas = MemoryCanvas::new(1, 1);
+    canvas.put_pixel(0, 0, &PixelColor::red());
+    let expected: Vec<u8> = [
+        &[137, 80, 78, 71, 13, 10, 26, 10][..],
+        &[0, 0, 0, 13], b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 2, 0, 0, 0], &[0x90, 0x77, 0x53, 0xde],
+        // zlib header, one stored block holding the filter byte and the pixel, Adler-32 of the data
+        &[0, 0, 0, 15], b"IDAT", &[0x78, 0x01, 1, 4, 0, 0xfb, 0xff, 0, 255, 0, 0, 0x03, 0x01, 0x01, 0x00], &[0x8d, 0x1d, 0xe5, 0x82],
+        &[0, 0, 0, 0], b"IEND", &[0xae, 0x42, 0x60, 0x82]
+    ].concat();
+    assert_eq!(bytes(write_png, &canvas), expected);
+}
@@ -1,96 +1,201 @@
 use crate::draw::Draw;
 use crate::pixel_color::PixelColor;
 use crate::point2d::Point2d;
-use crate::raw_canvas::RawCanvas;
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::line_style::LineStyle;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LineMode {
+    Aliased,
+    AntiAliased
+}
 
 #[derive(Copy, Clone)]
 pub struct Line {
     pub first: Point2d,
-    pub last: Point2d
+    pub last: Point2d,
+    pub color: PixelColor,
+    pub mode: LineMode,
+    pub compositing: Compositing,
+    pub style: LineStyle
 }
 
 impl Line {
     pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Line {
+        Line::with_color(PixelColor::red(), x1, y1, x2, y2)
+    }
+
+    pub fn with_color(color: PixelColor, x1: f32, y1: f32, x2: f32, y2: f32) -> Line {
         Line {
             first: Point2d::new(x1, y1),
-            last: Point2d::new(x2, y2)
+            last: Point2d::new(x2, y2),
+            color,
+            mode: LineMode::Aliased,
+            compositing: Compositing::source_over(),
+            style: LineStyle::Solid
         }
     }
-}
 
-impl Draw for Line {
-    fn draw(&self, canvas: &RawCanvas) {
-        let delta_y = self.last.y - self.first.y;
-        let delta_x = self.last.x - self.first.x;
-        let first_x = self.first.x as u32;
-        let first_y = self.first.y as u32;
-        let last_x = self.last.x as u32;
-        let last_y = self.last.y as u32;
-        let slope = delta_y / delta_x;
-        let mut x = first_x;
-        let mut y = first_y;
-        if first_y == last_y {
-            let left = if last_x > first_x {first_x} else {last_x};
-            let right = if last_x > first_x {last_x} else {first_x};
-            for x_pos in left..(right+1) {
-                canvas.put_pixel(x_pos, y, &PixelColor::red());
-            }
-        } else if first_x == last_x {
-            let bottom = if last_y > first_y {first_y} else {last_y};
-            let top = if last_y > first_y {last_y} else {first_y};
-            for y_pos in bottom..(top+1) {
-                canvas.put_pixel(x, y_pos, &PixelColor::red());
+    pub fn with_mode(&self, mode: LineMode) -> Line {
+        Line { mode, ..*self }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Line {
+        Line { compositing, ..*self }
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> Line {
+        Line { style, ..*self }
+    }
+
+    // Draws the line with its style pattern starting at phase, returns the phase at the last endpoint
+    // so consecutive lines of a polyline continue the pattern
+    pub fn draw_with_phase(&self, canvas: &mut dyn Canvas, phase: f32) -> f32 {
+        if !self.is_finite() {
+            return phase;
+        }
+        if let Some((visible, visible_phase)) = self.visible_part(canvas, phase) {
+            match self.mode {
+                LineMode::Aliased => visible.draw_aliased(canvas, visible_phase),
+                LineMode::AntiAliased => visible.draw_anti_aliased(canvas, visible_phase)
             }
-        } else {
-            if slope > 1.0 {
-                let mut denominator = 2.0 * delta_y - delta_x;
-                for _ in 0..(last_y - first_y) {
-                    canvas.put_pixel(x, y, &PixelColor::red());
-                    if denominator >= 0.0 {
-                        denominator -= 2.0 * delta_x;
-                    } else {
-                        x+= 1;
-                        denominator += 2.0 * delta_y - 2.0 * delta_x;
-                    }
-                    y += 1;
-                }
-            } else if slope > 0.0 {
-                let mut denominator = 2.0 * delta_y - delta_x;
-                for _ in 0..(last_x - first_x) {
-                    canvas.put_pixel(x, y, &PixelColor::red());
-                    if denominator >= 0.0 {
-                        y += 1;
-                        denominator += 2.0 * delta_y - 2.0 * delta_x;
-                    } else {
-                        denominator += 2.0 * delta_y;
-                    }
-                    x += 1;
-                }
-            } else if slope < -1.0 {
-                let mut denominator = delta_y + 2.0 * delta_x;
-                for _ in 0..(first_y - last_y) {
-                    canvas.put_pixel(x, y, &PixelColor::red());
-                    if denominator >= 0.0 {
-                        x += 1;
-                        denominator += 2.0 * delta_y + 2.0 * delta_x;
-                    } else {
-                        denominator += 2.0 * delta_x;
-                    }
-                    y -= 1;
-                }
-            } else {
-                let mut denominator = 2.0 * delta_y +  delta_x;
-                for _ in 0..(last_x - first_x) {
-                    canvas.put_pixel(x, y, &PixelColor::red());
-                    if denominator >= 0.0 {
-                        denominator += 2.0 * delta_y;
-                    } else {
-                        y -= 1;
-                        denominator += 2.0 * delta_x;
-                    }
-                    x += 1;
+        }
+        phase + self.style.measure(self.last.x - self.first.x, self.last.y - self.first.y)
+    }
+
+    fn is_finite(&self) -> bool {
+        self.first.x.is_finite() && self.first.y.is_finite() && self.last.x.is_finite() && self.last.y.is_finite()
+    }
+
+    // Liang-Barsky clip against the canvas grown by a pixel on every side, so the rasterizers never walk
+    // far outside of it and partially covered endpoint pixels of the cut stay invisible. Returns the part
+    // of the line with its own starting phase, lines lying inside keep their exact endpoints.
+    fn visible_part(&self, canvas: &dyn Canvas, phase: f32) -> Option<(Line, f32)> {
+        let (x0, y0) = (self.first.x as f64, self.first.y as f64);
+        let (dx, dy) = (self.last.x as f64 - x0, self.last.y as f64 - y0);
+        let (width, height) = (canvas.width() as f64, canvas.height() as f64);
+        // Boundary coordinate is kept exact, for long lines it cannot be recovered precisely from the parameter
+        let boundaries = [(-dx, x0 + 1.0, (Some(-1.0), None)), (dx, width + 1.0 - x0, (Some(width + 1.0), None)),
+                          (-dy, y0 + 1.0, (None, Some(-1.0))), (dy, height + 1.0 - y0, (None, Some(height + 1.0)))];
+        let (mut t_first, mut t_last) = (0.0f64, 1.0f64);
+        let (mut first_on, mut last_on) = ((None, None), (None, None));
+        for (p, q, boundary) in boundaries.iter() {
+            if *p == 0.0 {
+                if *q < 0.0 {
+                    return None;
                 }
+            } else if *p < 0.0 && q / p > t_first {
+                t_first = q / p;
+                first_on = *boundary;
+            } else if *p > 0.0 && q / p < t_last {
+                t_last = q / p;
+                last_on = *boundary;
+            }
+        }
+        if t_first > t_last {
+            return None;
+        }
+        let point_at = |t: f64, (x, y): (Option<f64>, Option<f64>)| {
+            Point2d::new(x.unwrap_or(x0 + t * dx) as f32, y.unwrap_or(y0 + t * dy) as f32)
+        };
+        let first = if t_first > 0.0 { point_at(t_first, first_on) } else { self.first };
+        let last = if t_last < 1.0 { point_at(t_last, last_on) } else { self.last };
+        // Bresenham measures the pattern between pixels, Wu between the exact points
+        let skipped = match self.mode {
+            LineMode::Aliased => self.style.measure(((first.x as f64).floor() - x0.floor()) as f32,
+                                                    ((first.y as f64).floor() - y0.floor()) as f32),
+            LineMode::AntiAliased => self.style.measure((t_first * dx) as f32, (t_first * dy) as f32)
+        };
+        Some((Line { first, last, ..*self }, phase + skipped))
+    }
+
+    fn draw_aliased(&self, canvas: &mut dyn Canvas, phase: f32) {
+        let mut first = (self.first.x.floor() as i64, self.first.y.floor() as i64);
+        let mut last = (self.last.x.floor() as i64, self.last.y.floor() as i64);
+        let start = first;
+        // Always walk from the lower endpoint so both endpoint orders produce the same pixels
+        if (first.1, first.0) > (last.1, last.0) {
+            std::mem::swap(&mut first, &mut last);
+        }
+        let delta_x = (last.0 - first.0).abs();
+        let delta_y = -(last.1 - first.1).abs();
+        let step_x = if first.0 < last.0 { 1 } else { -1 };
+        let step_y = if first.1 < last.1 { 1 } else { -1 };
+        let mut error = delta_x + delta_y;
+        let (mut x, mut y) = first;
+        loop {
+            if self.style.is_on(phase + self.style.measure((x - start.0) as f32, (y - start.1) as f32)) {
+                canvas.composite_pixel(x as i32, y as i32, &self.color, 1.0, &self.compositing);
+            }
+            if x == last.0 && y == last.1 {
+                break;
             }
+            let doubled_error = 2 * error;
+            if doubled_error >= delta_y {
+                error += delta_y;
+                x += step_x;
+            }
+            if doubled_error <= delta_x {
+                error += delta_x;
+                y += step_y;
+            }
+        }
+    }
+
+    // Xiaolin Wu's algorithm, pixel centers are shifted by half a pixel to match put_pixel_f
+    fn draw_anti_aliased(&self, canvas: &mut dyn Canvas, phase: f32) {
+        let (mut x0, mut y0) = (self.first.x - 0.5, self.first.y - 0.5);
+        let (mut x1, mut y1) = (self.last.x - 0.5, self.last.y - 0.5);
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
         }
+        let delta_x = x1 - x0;
+        let gradient = if delta_x == 0.0 { 1.0 } else { (y1 - y0) / delta_x };
+        let major_start = if steep { self.first.y } else { self.first.x } - 0.5;
+        let mut plot = |x: f32, y: f32, coverage: f32| {
+            let distance = (x - major_start).abs();
+            if self.style.is_on(phase + self.style.measure(distance, distance * gradient)) {
+                let (px, py) = if steep { (y, x) } else { (x, y) };
+                canvas.composite_pixel(px as i32, py as i32, &self.color, coverage, &self.compositing);
+            }
+        };
+
+        let first_x = x0.round();
+        let first_y = y0 + gradient * (first_x - x0);
+        let first_gap = 1.0 - fraction(x0 + 0.5);
+        plot(first_x, first_y.floor(), (1.0 - fraction(first_y)) * first_gap);
+        plot(first_x, first_y.floor() + 1.0, fraction(first_y) * first_gap);
+
+        let last_x = x1.round();
+        let last_y = y1 + gradient * (last_x - x1);
+        let last_gap = fraction(x1 + 0.5);
+        plot(last_x, last_y.floor(), (1.0 - fraction(last_y)) * last_gap);
+        plot(last_x, last_y.floor() + 1.0, fraction(last_y) * last_gap);
+
+        let mut intersection_y = first_y + gradient;
+        let mut x = first_x + 1.0;
+        while x < last_x {
+            plot(x, intersection_y.floor(), 1.0 - fraction(intersection_y));
+            plot(x, intersection_y.floor() + 1.0, fraction(intersection_y));
+            intersection_y += gradient;
+            x += 1.0;
+        }
+    }
+}
+
+fn fraction(value: f32) -> f32 {
+    value - value.floor()
+}
+
+impl Draw for Line {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        self.draw_with_phase(canvas, 0.0);
     }
 }
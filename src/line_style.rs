@@ -0,0 +1,133 @@
+use crate::point2d::Point2d;
+
+pub const MAX_DASHES: usize = 8;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LineStyle {
+    Solid,
+    // Alternating lengths of drawn and skipped parts, odd arrays are repeated twice like in SVG
+    Dashed { dashes: [f32; MAX_DASHES], count: usize, offset: f32 },
+    // Every bit of the pattern, starting from the lowest one, covers factor pixels
+    Stipple { pattern: u16, factor: u32 }
+}
+
+impl LineStyle {
+    // At most MAX_DASHES lengths, all of them finite and non-negative with a positive sum. None for
+    // other arrays, which describe no pattern that could be drawn.
+    pub fn dashed(dashes: &[f32], offset: f32) -> Option<LineStyle> {
+        let count = dashes.len();
+        let valid = dashes.iter().all(|d| *d >= 0.0 && d.is_finite());
+        if count == 0 || count > MAX_DASHES || !valid || dashes.iter().sum::<f32>() <= 0.0 {
+            return None;
+        }
+        let mut array = [0.0; MAX_DASHES];
+        array[..count].copy_from_slice(dashes);
+        Some(LineStyle::Dashed { dashes: array, count, offset })
+    }
+
+    pub fn dotted(spacing: f32) -> Option<LineStyle> {
+        LineStyle::dashed(&[1.0, spacing], 0.0)
+    }
+
+    pub fn stipple(pattern: u16, factor: u32) -> LineStyle {
+        LineStyle::Stipple { pattern, factor: factor.max(1) }
+    }
+
+    // Dashes are measured along the line, stipple bits advance once per rasterized pixel
+    pub fn measure(&self, delta_x: f32, delta_y: f32) -> f32 {
+        match self {
+            LineStyle::Stipple { .. } => delta_x.abs().max(delta_y.abs()),
+            _ => (delta_x * delta_x + delta_y * delta_y).sqrt()
+        }
+    }
+
+    fn dash_entries(dashes: &[f32; MAX_DASHES], count: usize) -> impl Iterator<Item = (usize, f32)> + '_ {
+        let entries = if count % 2 == 1 { count * 2 } else { count };
+        (0..entries).map(move |i| (i, dashes[i % count]))
+    }
+
+    pub fn is_on(&self, phase: f32) -> bool {
+        match self {
+            LineStyle::Solid => true,
+            LineStyle::Dashed { dashes, count, offset } => {
+                let period: f32 = LineStyle::dash_entries(dashes, *count).map(|(_, d)| d).sum();
+                let mut position = (phase + offset).rem_euclid(period);
+                for (index, dash) in LineStyle::dash_entries(dashes, *count) {
+                    if position < dash {
+                        return index % 2 == 0;
+                    }
+                    position -= dash;
+                }
+                false
+            }
+            LineStyle::Stipple { pattern, factor } => {
+                let bit = ((phase.max(0.0) / *factor as f32).floor() as u64 % 16) as u16;
+                (pattern >> bit) & 1 == 1
+            }
+        }
+    }
+
+    // Smallest phase bigger than the given one at which the pattern may switch between drawn and skipped
+    pub fn next_change(&self, phase: f32) -> f32 {
+        match self {
+            LineStyle::Solid => f32::INFINITY,
+            LineStyle::Dashed { dashes, count, offset } => {
+                let period: f32 = LineStyle::dash_entries(dashes, *count).map(|(_, d)| d).sum();
+                let shifted = phase + offset;
+                let cycle_start = (shifted / period).floor() * period;
+                let mut boundary = cycle_start;
+                for (_, dash) in LineStyle::dash_entries(dashes, *count) {
+                    boundary += dash;
+                    if boundary > shifted {
+                        return boundary - offset;
+                    }
+                }
+                cycle_start + period + dashes[0] - offset
+            }
+            LineStyle::Stipple { factor, .. } => {
+                let factor = *factor as f32;
+                ((phase / factor).floor() + 1.0) * factor
+            }
+        }
+    }
+
+    // Splits polyline into drawn runs, the phase continues over the vertices
+    pub fn split_polyline(&self, points: &[Point2d], closed: bool) -> Vec<Vec<Point2d>> {
+        let length = points.len();
+        let count = if closed && length > 2 { length } else { length.saturating_sub(1) };
+        let mut runs: Vec<Vec<Point2d>> = Vec::new();
+        let mut current: Vec<Point2d> = Vec::new();
+        let mut phase = 0.0;
+        for i in 0..count {
+            let from = points[i];
+            let to = points[(i + 1) % length];
+            let segment_length = self.measure(to.x - from.x, to.y - from.y);
+            if segment_length <= 0.0 {
+                continue;
+            }
+            let at = |t: f32| Point2d::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+            let mut position = 0.0;
+            while position < segment_length {
+                let mut end = (self.next_change(phase + position) - phase).min(segment_length);
+                if end <= position {
+                    end = (position + 1e-3).min(segment_length);
+                }
+                if self.is_on(phase + (position + end) / 2.0) {
+                    if current.is_empty() {
+                        current.push(at(position / segment_length));
+                    }
+                    current.push(at(end / segment_length));
+                } else if !current.is_empty() {
+                    runs.push(current);
+                    current = Vec::new();
+                }
+                position = end;
+            }
+            phase += segment_length;
+        }
+        if !current.is_empty() {
+            runs.push(current);
+        }
+        runs
+    }
+}
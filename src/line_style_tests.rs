@@ -0,0 +1,49 @@
+use crate::line::{Line, LineMode};
+use crate::line_style::{LineStyle, MAX_DASHES};
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::stroke::{LineCap, Stroke};
+use crate::golden_tests::assert_golden;
+
+#[test]
+fn dash_arrays_longer_than_the_limit() {
+    let dashes = [2.0; MAX_DASHES + 1];
+    assert_eq!(LineStyle::dashed(&dashes, 0.0), None);
+    match LineStyle::dashed(&dashes[..MAX_DASHES], 0.0) {
+        Some(LineStyle::Dashed { count, .. }) => assert_eq!(count, MAX_DASHES),
+        style => panic!("unexpected style {:?}", style)
+    }
+}
+
+#[test]
+fn dash_arrays_without_pattern() {
+    assert_eq!(LineStyle::dashed(&[], 0.0), None);
+    assert_eq!(LineStyle::dashed(&[4.0, -1.0], 0.0), None);
+    assert_eq!(LineStyle::dashed(&[4.0, f32::NAN], 0.0), None);
+    assert_eq!(LineStyle::dashed(&[f32::INFINITY, 2.0], 0.0), None);
+    assert_eq!(LineStyle::dashed(&[0.0, 0.0], 0.0), None);
+    assert_eq!(LineStyle::dotted(-1.0), None);
+    // Zero lengths are fine as long as the pattern has some length
+    assert!(LineStyle::dashed(&[0.0, 2.0], 0.0).is_some());
+}
+
+#[test]
+fn line_styles() {
+    let outline = Polygon::new(PixelColor::rgb(255, 255, 0), vec![Point2d::new(34.0, 34.0), Point2d::new(34.0, 60.0),
+                                                                  Point2d::new(60.0, 60.0), Point2d::new(60.0, 34.0)]);
+    assert_golden("line_styles", vec![
+        Box::new(Line::new(2.0, 4.0, 62.0, 4.0).with_style(LineStyle::dashed(&[6.0, 3.0], 2.0).unwrap())),
+        Box::new(Line::new(2.0, 8.0, 62.0, 20.0).with_style(LineStyle::dashed(&[5.0, 2.0, 1.0], 0.0).unwrap())),
+        Box::new(Line::new(2.0, 24.0, 62.0, 24.0).with_style(LineStyle::dotted(2.0).unwrap())),
+        Box::new(Line::new(2.0, 28.0, 62.0, 28.0).with_style(LineStyle::stipple(0b1111_0000_1100_1010, 1))),
+        Box::new(Line::new(4.0, 34.0, 28.0, 60.0).with_style(LineStyle::dashed(&[4.0, 4.0], 0.0).unwrap())
+            .with_mode(LineMode::AntiAliased)),
+        Box::new(outline.outline().with_style(LineStyle::dashed(&[7.0, 3.0], 0.0).unwrap())),
+        Box::new(Polyline::new(PixelColor::rgb(0, 255, 255), vec![Point2d::new(38.0, 38.0), Point2d::new(56.0, 42.0),
+                                                                  Point2d::new(40.0, 56.0)])
+            .with_style(LineStyle::dashed(&[6.0, 3.0], 0.0).unwrap())
+            .with_stroke(Stroke::new(2.0).with_cap(LineCap::Square)))
+    ]);
+}
@@ -0,0 +1,89 @@
+use crate::canvas::Canvas;
+use crate::draw::Draw;
+use crate::line::{Line, LineMode};
+use crate::line_style::LineStyle;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden};
+
+#[test]
+fn lines_axis_aligned() {
+    assert_golden("lines_axis_aligned", vec![
+        Box::new(Line::new(4.0, 10.3, 60.0, 10.3)),
+        Box::new(Line::new(50.0, 4.0, 50.0, 60.0)),
+        Box::new(Line::new(40.0, 30.0, 8.0, 30.0)),
+        Box::new(Line::new(20.0, 58.0, 20.0, 34.0))
+    ]);
+}
+
+#[test]
+fn lines_sloped() {
+    assert_golden("lines_sloped", vec![
+        Box::new(Line::new(3.0, 60.3, 8.0, 2.0)),
+        Box::new(Line::new(3.0, 2.3, 8.0, 60.0)),
+        Box::new(Line::new(3.0, 2.3, 60.0, 9.0)),
+        Box::new(Line::new(3.0, 60.3, 60.0, 2.0)),
+        Box::new(Line::new(10.0, 10.0, 55.0, 55.0))
+    ]);
+}
+
+#[test]
+fn lines_all_octants() {
+    let mut drawables: Vec<Box<dyn Draw>> = Vec::new();
+    let ends = [(60.0, 40.0), (44.0, 60.0), (20.0, 60.0), (4.0, 40.0),
+                (4.0, 24.0), (20.0, 4.0), (44.0, 4.0), (60.0, 24.0)];
+    for (index, (x, y)) in ends.iter().enumerate() {
+        let color = PixelColor::rgb(255, (index * 30) as u8, 0);
+        if index % 2 == 0 {
+            drawables.push(Box::new(Line::with_color(color, 32.0, 32.0, *x, *y)));
+        } else {
+            drawables.push(Box::new(Line::with_color(color, *x, *y, 32.0, 32.0)));
+        }
+    }
+    assert_golden("lines_all_octants", drawables);
+}
+
+#[test]
+fn lines_reversed_and_negative() {
+    assert_golden("lines_reversed_and_negative", vec![
+        Box::new(Line::new(60.0, 2.0, 3.0, 20.0)),
+        Box::new(Line::new(40.0, 60.0, 30.0, 5.0)),
+        Box::new(Line::new(-20.0, -10.0, 30.0, 40.0)),
+        Box::new(Line::new(50.0, 70.0, 70.0, 30.0))
+    ]);
+}
+
+#[test]
+fn lines_anti_aliased() {
+    let lines = [Line::new(3.0, 60.3, 8.0, 2.0), Line::new(3.0, 2.3, 60.0, 9.0),
+                 Line::new(60.0, 60.0, 4.5, 30.2), Line::new(32.0, 4.0, 32.0, 40.0),
+                 Line::with_color(PixelColor::rgb(0, 255, 0).with_alpha(128), 10.0, 50.0, 58.0, 20.0)];
+    let drawables: Vec<Box<dyn Draw>> = lines.iter()
+        .map(|line| Box::new(line.with_mode(LineMode::AntiAliased)) as Box<dyn Draw>)
+        .collect();
+    assert_golden("lines_anti_aliased", drawables);
+}
+
+// Lines reaching far outside of the canvas are cut before rasterization and keep the style phase of the whole line
+#[test]
+fn lines_clipped_to_canvas() {
+    let style = LineStyle::dashed(&[3.0, 2.0], 0.0).unwrap();
+    for mode in [LineMode::Aliased, LineMode::AntiAliased].iter() {
+        for (x1, y1, x2, y2) in [(-40.5, 10.5, 90.5, 10.5), (-40.5, -20.5, 90.5, 110.5)].iter() {
+            let mut clipped = MemoryCanvas::new(WIDTH, HEIGHT);
+            Line::new(*x1, *y1, *x2, *y2).with_style(style).with_mode(*mode).draw(&mut clipped);
+            // The same line shifted far enough to lie completely inside of a larger canvas
+            let mut whole = MemoryCanvas::new(WIDTH * 3, HEIGHT * 3);
+            Line::new(x1 + 64.0, y1 + 64.0, x2 + 64.0, y2 + 64.0).with_style(style).with_mode(*mode).draw(&mut whole);
+            for y in 0..HEIGHT as i32 {
+                for x in 0..WIDTH as i32 {
+                    assert_eq!(clipped.get_pixel(x, y), whole.get_pixel(x + 64, y + 64), "{:?} pixel ({}, {})", mode, x, y);
+                }
+            }
+        }
+    }
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Line::new(-1e30, 5.0, 1e30, 5.0).draw(&mut canvas);
+    Line::new(-2e8, 20.0, 2e8, 21.0).with_mode(LineMode::AntiAliased).draw(&mut canvas);
+    assert!((0..WIDTH as i32).all(|x| canvas.get_pixel(x, 5) == Some(PixelColor::red())));
+}
@@ -5,10 +5,66 @@ mod point2d;
 mod line;
 mod pixel_color;
 mod clipping;
+mod boolean;
 mod vector2d;
+mod canvas;
+mod memory_canvas;
+mod compositing;
+mod stroke;
+mod polyline;
+mod line_style;
+mod circle;
+mod ellipse;
+mod arc;
+mod bezier;
+mod path;
+mod paint;
+mod pattern;
+mod flood_fill;
+mod blit;
+mod transform;
+mod image_export;
+#[cfg(test)]
+mod golden_tests;
+#[cfg(test)]
+mod clipping_tests;
+#[cfg(test)]
+mod image_export_tests;
+#[cfg(test)]
+mod line_style_tests;
+#[cfg(test)]
+mod canvas_tests;
+#[cfg(test)]
+mod line_tests;
+#[cfg(test)]
+mod polygon_tests;
+#[cfg(test)]
+mod compositing_tests;
+#[cfg(test)]
+mod stroke_tests;
+#[cfg(test)]
+mod circle_tests;
+#[cfg(test)]
+mod arc_tests;
+#[cfg(test)]
+mod bezier_tests;
+#[cfg(test)]
+mod path_tests;
+#[cfg(test)]
+mod paint_tests;
+#[cfg(test)]
+mod pattern_tests;
+#[cfg(test)]
+mod flood_fill_tests;
+#[cfg(test)]
+mod blit_tests;
+#[cfg(test)]
+mod boolean_tests;
 
 extern crate sdl2;
 
+use std::path::Path;
+
 use sdl2::EventPump;
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
@@ -19,12 +75,15 @@ use sdl2_sys::{SDL_PixelFormat, wchar_t};
 use sdl2_sys::SDL_Surface;
 
 use raw_canvas::RawCanvas;
-use line::Line;
+use canvas::Canvas;
+use line::{Line, LineMode};
 use point2d::Point2d;
 use crate::clipping::{ClippingRectangle, cohen_sutherland_line_clip, cyrus_beck_line_clip, sutherland_hodgman_polygon_clip};
 use crate::draw::Draw;
+use crate::image_export::save_image;
 use crate::pixel_color::PixelColor;
 use crate::polygon::Polygon;
+use crate::line_style::LineStyle;
 
 struct Context {
     window: Window,
@@ -44,17 +103,25 @@ fn create_context() -> Result<Context, String> {
 fn draw(context: &mut Context, drawables: &Vec<Box<dyn Draw>>) {
     let mut quit = false;
     while !quit {
+        let mut save_frame = false;
         for event in context.event_pump.poll_iter() {
             match event {
                 Event::KeyDown { keycode: Some(Keycode::Escape), ..} => quit = true,
+                Event::KeyDown { keycode: Some(Keycode::S), ..} => save_frame = true,
                 _ => {}
             }
         }
         let window_surface = context.window.surface(&context.event_pump).expect("Error");
-        let raw_canvas = RawCanvas::new(&window_surface);
+        let mut raw_canvas = RawCanvas::new(&window_surface);
         raw_canvas.clean_color(&PixelColor::black());
         for drawable in drawables.iter() {
-            drawable.draw(&raw_canvas);
+            drawable.draw(&mut raw_canvas);
+        }
+        if save_frame {
+            match save_image(&raw_canvas, Path::new("frame.png")) {
+                Ok(_) => println!("Frame saved to frame.png"),
+                Err(err) => println!("Unable to save frame: {}", err),
+            }
         }
         window_surface.update_window().expect("Unable to update window");
     }
@@ -91,11 +158,15 @@ fn main() {
     let polygon = Polygon::new(PixelColor::red(), vec![Point2d::new(50.0, 20.0),
                                                        Point2d::new(15.0, 150.0), Point2d::new(60.0, 250.0),
                                                        Point2d::new(250.0, 140.0)]);
-    let clipped = sutherland_hodgman_polygon_clip(&polygon, &vec![Point2d::new(30.0, 30.0),
-                                                                   Point2d::new(30.0, 200.0),
-                                                                   Point2d::new(200.0, 200.0),
-                                                                   Point2d::new(200.0, 30.0)]).unwrap();
-    drawables.push(Box::new(clipped));
+    let clipping_window = vec![Point2d::new(30.0, 30.0),
+                               Point2d::new(30.0, 200.0),
+                               Point2d::new(200.0, 200.0),
+                               Point2d::new(200.0, 30.0)];
+    let clipped = sutherland_hodgman_polygon_clip(&polygon, &clipping_window).unwrap();
+    drawables.push(Box::new(clipped.with_mode(LineMode::AntiAliased)));
+    let clipping_window_overlay = Polygon::new(PixelColor::rgb(0, 0, 255).with_alpha(64), clipping_window);
+    drawables.push(Box::new(clipping_window_overlay.outline().with_style(LineStyle::dashed(&[8.0, 4.0], 0.0).unwrap())));
+    drawables.push(Box::new(clipping_window_overlay));
     match init_result {
         Ok(mut context) => draw(&mut context, &drawables),
         Err(err) => println!("Error occurred during context init: {}", err),
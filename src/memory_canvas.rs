@@ -0,0 +1,41 @@
+use crate::canvas::{Canvas, PixelFormat};
+
+pub struct MemoryCanvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u32>,
+    format: PixelFormat
+}
+
+impl MemoryCanvas {
+    pub fn new(width: u32, height: u32) -> MemoryCanvas {
+        MemoryCanvas::with_format(width, height, PixelFormat::argb8888())
+    }
+
+    pub fn with_format(width: u32, height: u32, format: PixelFormat) -> MemoryCanvas {
+        let pixels = vec![0; (width * height) as usize];
+        MemoryCanvas { width, height, pixels, format }
+    }
+}
+
+impl Canvas for MemoryCanvas {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn format(&self) -> &PixelFormat {
+        &self.format
+    }
+
+    fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.pixels
+    }
+}
@@ -0,0 +1,174 @@
+use std::f32::consts::PI;
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::pattern::Pattern;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SpreadMode {
+    Pad,
+    Repeat,
+    Reflect
+}
+
+impl SpreadMode {
+    fn apply(&self, t: f32) -> f32 {
+        match self {
+            SpreadMode::Pad => t.clamp(0.0, 1.0),
+            SpreadMode::Repeat => t.rem_euclid(1.0),
+            SpreadMode::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 { 2.0 - t } else { t }
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ColorStop {
+    pub offset: f32,
+    pub color: PixelColor
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Gradient {
+    // Sorted by offset
+    pub stops: Vec<ColorStop>,
+    pub spread: SpreadMode
+}
+
+impl Gradient {
+    pub fn new(from: PixelColor, to: PixelColor) -> Gradient {
+        Gradient {
+            stops: vec![ColorStop { offset: 0.0, color: from }, ColorStop { offset: 1.0, color: to }],
+            spread: SpreadMode::Pad
+        }
+    }
+
+    // Stops with equal offsets keep their insertion order, which gives hard color transitions
+    pub fn with_stop(&self, offset: f32, color: PixelColor) -> Gradient {
+        let mut stops = self.stops.clone();
+        let index = stops.iter().position(|stop| stop.offset > offset).unwrap_or(stops.len());
+        stops.insert(index, ColorStop { offset, color });
+        Gradient { stops, ..self.clone() }
+    }
+
+    pub fn with_spread(&self, spread: SpreadMode) -> Gradient {
+        Gradient { spread, ..self.clone() }
+    }
+
+    pub fn color_at(&self, t: f32) -> PixelColor {
+        let t = self.spread.apply(t);
+        let first = self.stops[0];
+        if t <= first.offset {
+            return first.color;
+        }
+        for pair in self.stops.windows(2) {
+            if t <= pair[1].offset {
+                let length = pair[1].offset - pair[0].offset;
+                let ratio = if length > 0.0 { (t - pair[0].offset) / length } else { 1.0 };
+                return interpolate(&pair[0].color, &pair[1].color, ratio);
+            }
+        }
+        self.stops[self.stops.len() - 1].color
+    }
+}
+
+fn interpolate(from: &PixelColor, to: &PixelColor, ratio: f32) -> PixelColor {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+    PixelColor::new(channel(from.red, to.red), channel(from.green, to.green), channel(from.blue, to.blue),
+                    channel(from.alpha, to.alpha))
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Paint {
+    Solid(PixelColor),
+    // Offset 0 at start, 1 at end, constant along lines perpendicular to them
+    Linear { start: Point2d, end: Point2d, gradient: Gradient },
+    // Offset 0 at focal point, 1 on the circle
+    Radial { center: Point2d, radius: f32, focal: Point2d, gradient: Gradient },
+    // Offset grows counter clockwise from the start angle and reaches 1 after full turn
+    Conic { center: Point2d, start_angle: f32, gradient: Gradient },
+    Pattern(Pattern)
+}
+
+impl Paint {
+    pub fn linear(start: Point2d, end: Point2d, gradient: Gradient) -> Paint {
+        Paint::Linear { start, end, gradient }
+    }
+
+    pub fn radial(center: Point2d, radius: f32, gradient: Gradient) -> Paint {
+        Paint::Radial { center, radius, focal: center, gradient }
+    }
+
+    // Focal point outside of the circle is moved just inside of it
+    pub fn radial_with_focal(center: Point2d, radius: f32, focal: Point2d, gradient: Gradient) -> Paint {
+        let (dx, dy) = (focal.x - center.x, focal.y - center.y);
+        let distance = (dx * dx + dy * dy).sqrt();
+        let limit = radius * 0.999;
+        let focal = if distance > limit {
+            Point2d::new(center.x + dx * limit / distance, center.y + dy * limit / distance)
+        } else {
+            focal
+        };
+        Paint::Radial { center, radius, focal, gradient }
+    }
+
+    pub fn conic(center: Point2d, start_angle: f32, gradient: Gradient) -> Paint {
+        Paint::Conic { center, start_angle, gradient }
+    }
+
+    pub fn color_at(&self, x: f32, y: f32) -> PixelColor {
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Linear { start, end, gradient } => {
+                let (dx, dy) = (end.x - start.x, end.y - start.y);
+                let length = dx * dx + dy * dy;
+                let t = if length > 0.0 { ((x - start.x) * dx + (y - start.y) * dy) / length } else { 0.0 };
+                gradient.color_at(t)
+            }
+            Paint::Radial { center, radius, focal, gradient } => {
+                // Circle growing from the focal point towards the gradient circle passing through (x, y)
+                let (dx, dy) = (center.x - focal.x, center.y - focal.y);
+                let (qx, qy) = (x - focal.x, y - focal.y);
+                let a = dx * dx + dy * dy - radius * radius;
+                let b = qx * dx + qy * dy;
+                let c = qx * qx + qy * qy;
+                let t = if a.abs() > f32::EPSILON {
+                    (b - (b * b - a * c).max(0.0).sqrt()) / a
+                } else if b.abs() > f32::EPSILON {
+                    c / (2.0 * b)
+                } else {
+                    0.0
+                };
+                gradient.color_at(t)
+            }
+            Paint::Conic { center, start_angle, gradient } => {
+                let angle = (y - center.y).atan2(x - center.x) - start_angle;
+                gradient.color_at((angle / (2.0 * PI)).rem_euclid(1.0))
+            }
+            Paint::Pattern(pattern) => pattern.color_at(x, y)
+        }
+    }
+
+    // Paint is sampled at pixel centers
+    pub fn fill_span(&self, canvas: &mut dyn Canvas, y: i32, x_from: i32, x_to: i32, compositing: &Compositing) {
+        match self {
+            Paint::Solid(color) => canvas.fill_span(y, x_from, x_to, color, compositing),
+            _ => {
+                let x_from = x_from.max(0);
+                let x_to = x_to.min(canvas.width() as i32 - 1);
+                for x in x_from..=x_to {
+                    let color = self.color_at(x as f32 + 0.5, y as f32 + 0.5);
+                    canvas.composite_pixel(x, y, &color, 1.0, compositing);
+                }
+            }
+        }
+    }
+
+    pub fn composite_pixel(&self, canvas: &mut dyn Canvas, x: i32, y: i32, coverage: f32, compositing: &Compositing) {
+        let color = self.color_at(x as f32 + 0.5, y as f32 + 0.5);
+        canvas.composite_pixel(x, y, &color, coverage, compositing);
+    }
+}
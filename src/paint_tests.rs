@@ -0,0 +1,40 @@
+use crate::line::LineMode;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::paint::{Gradient, Paint, SpreadMode};
+use crate::path::Path;
+use crate::polygon::Polygon;
+use crate::polyline::Polyline;
+use crate::stroke::{LineJoin, Stroke};
+use crate::golden_tests::assert_golden;
+
+#[test]
+fn gradient_paints() {
+    let rectangle = |x: f32, y: f32| vec![Point2d::new(x, y), Point2d::new(x + 28.0, y), Point2d::new(x + 28.0, y + 28.0),
+                                           Point2d::new(x, y + 28.0)];
+    let rainbow = Gradient::new(PixelColor::red(), PixelColor::rgb(0, 0, 255)).with_stop(0.5, PixelColor::rgb(0, 255, 0));
+    let linear = Paint::linear(Point2d::new(6.0, 0.0), Point2d::new(26.0, 0.0), rainbow.clone());
+    let radial = Paint::radial_with_focal(Point2d::new(48.0, 16.0), 8.0, Point2d::new(44.0, 20.0),
+                                          Gradient::new(PixelColor::rgb(255, 255, 255), PixelColor::rgb(0, 0, 255))
+                                              .with_spread(SpreadMode::Reflect));
+    let conic = Paint::conic(Point2d::new(16.0, 48.0), 0.0, rainbow.with_stop(1.0, PixelColor::red()));
+    let stripes = Paint::linear(Point2d::new(36.0, 36.0), Point2d::new(42.0, 42.0),
+                                Gradient::new(PixelColor::rgb(255, 255, 0), PixelColor::rgb(255, 0, 255))
+                                    .with_spread(SpreadMode::Repeat));
+    assert_golden("gradient_paints", vec![
+        Box::new(Polygon::new(PixelColor::black(), rectangle(2.0, 2.0)).with_paint(linear)),
+        Box::new(Polygon::new(PixelColor::black(), rectangle(34.0, 2.0)).with_paint(radial)),
+        Box::new(Path::new(PixelColor::black())
+            .move_to(Point2d::new(3.0, 48.0))
+            .arc_to(13.0, 13.0, 0.0, false, true, Point2d::new(29.0, 48.0))
+            .arc_to(13.0, 13.0, 0.0, false, true, Point2d::new(3.0, 48.0))
+            .close()
+            .with_fill(true)
+            .with_mode(LineMode::AntiAliased)
+            .with_paint(conic)),
+        Box::new(Polyline::new(PixelColor::black(), vec![Point2d::new(38.0, 38.0), Point2d::new(60.0, 44.0),
+                                                         Point2d::new(40.0, 60.0)])
+            .with_stroke(Stroke::new(5.0).with_join(LineJoin::Round))
+            .with_paint(stripes))
+    ]);
+}
@@ -0,0 +1,242 @@
+use std::f32::consts::PI;
+use crate::arc::Arc;
+use crate::bezier::{DEFAULT_TOLERANCE, flatten_cubic, flatten_quadratic};
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::line::LineMode;
+use crate::line_style::LineStyle;
+use crate::paint::Paint;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::{FillRule, fill_rings};
+use crate::polyline::Polyline;
+use crate::stroke::Stroke;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PathCommand {
+    MoveTo(Point2d),
+    LineTo(Point2d),
+    QuadTo { control: Point2d, end: Point2d },
+    CubicTo { first_control: Point2d, second_control: Point2d, end: Point2d },
+    // Elliptical arc to the end point, parametrized like in SVG. Sweep set means the arc goes
+    // in the direction of growing angles, counter clockwise on the canvas
+    ArcTo { radius_x: f32, radius_y: f32, rotation: f32, large_arc: bool, sweep: bool, end: Point2d },
+    Close
+}
+
+// Flattened subpath
+pub struct Subpath {
+    pub points: Vec<Point2d>,
+    pub closed: bool
+}
+
+#[derive(Clone)]
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+    pub color: PixelColor,
+    pub filled: bool,
+    pub fill_rule: FillRule,
+    pub tolerance: f32,
+    pub mode: LineMode,
+    pub style: LineStyle,
+    pub compositing: Compositing,
+    pub stroke: Option<Stroke>,
+    // Overrides color of the fill and of the thick stroke when set
+    pub paint: Option<Paint>
+}
+
+impl Path {
+    pub fn new(color: PixelColor) -> Path {
+        Path {
+            commands: Vec::new(),
+            color,
+            filled: false,
+            fill_rule: FillRule::NonZero,
+            tolerance: DEFAULT_TOLERANCE,
+            mode: LineMode::Aliased,
+            style: LineStyle::Solid,
+            compositing: Compositing::source_over(),
+            stroke: None,
+            paint: None
+        }
+    }
+
+    pub fn move_to(mut self, point: Point2d) -> Path {
+        self.commands.push(PathCommand::MoveTo(point));
+        self
+    }
+
+    pub fn line_to(mut self, point: Point2d) -> Path {
+        self.commands.push(PathCommand::LineTo(point));
+        self
+    }
+
+    pub fn quad_to(mut self, control: Point2d, end: Point2d) -> Path {
+        self.commands.push(PathCommand::QuadTo { control, end });
+        self
+    }
+
+    pub fn cubic_to(mut self, first_control: Point2d, second_control: Point2d, end: Point2d) -> Path {
+        self.commands.push(PathCommand::CubicTo { first_control, second_control, end });
+        self
+    }
+
+    pub fn arc_to(mut self, radius_x: f32, radius_y: f32, rotation: f32, large_arc: bool, sweep: bool, end: Point2d) -> Path {
+        self.commands.push(PathCommand::ArcTo { radius_x, radius_y, rotation, large_arc, sweep, end });
+        self
+    }
+
+    pub fn close(mut self) -> Path {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    pub fn with_fill(&self, filled: bool) -> Path {
+        Path { filled, ..self.clone() }
+    }
+
+    pub fn with_fill_rule(&self, fill_rule: FillRule) -> Path {
+        Path { fill_rule, ..self.clone() }
+    }
+
+    pub fn with_tolerance(&self, tolerance: f32) -> Path {
+        Path { tolerance, ..self.clone() }
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> Path {
+        Path { mode, ..self.clone() }
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> Path {
+        Path { style, ..self.clone() }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Path {
+        Path { compositing, ..self.clone() }
+    }
+
+    pub fn with_stroke(&self, stroke: Stroke) -> Path {
+        Path { stroke: Some(stroke), ..self.clone() }
+    }
+
+    pub fn with_paint(&self, paint: Paint) -> Path {
+        Path { paint: Some(paint), ..self.clone() }
+    }
+
+    // Commands drawn before the first move start at the origin
+    pub fn subpaths(&self) -> Vec<Subpath> {
+        let mut result: Vec<Subpath> = Vec::new();
+        let mut points: Vec<Point2d> = Vec::new();
+        let mut start = Point2d::zero();
+        let mut current = Point2d::zero();
+        for command in self.commands.iter() {
+            if points.is_empty() && !matches!(command, PathCommand::MoveTo(_) | PathCommand::Close) {
+                points.push(current);
+            }
+            match command {
+                PathCommand::MoveTo(point) => {
+                    if points.len() > 1 {
+                        result.push(Subpath { points, closed: false });
+                    }
+                    points = vec![*point];
+                    start = *point;
+                }
+                PathCommand::LineTo(point) => points.push(*point),
+                PathCommand::QuadTo { control, end } => flatten_quadratic(&current, control, end, self.tolerance, &mut points),
+                PathCommand::CubicTo { first_control, second_control, end } => {
+                    flatten_cubic(&current, first_control, second_control, end, self.tolerance, &mut points)
+                }
+                PathCommand::ArcTo { radius_x, radius_y, rotation, large_arc, sweep, end } => {
+                    arc_points(&current, *radius_x, *radius_y, *rotation, *large_arc, *sweep, end, &mut points)
+                }
+                PathCommand::Close => {
+                    if points.len() > 1 {
+                        result.push(Subpath { points, closed: true });
+                    }
+                    points = Vec::new();
+                    current = start;
+                    continue;
+                }
+            }
+            current = *points.last().unwrap();
+        }
+        if points.len() > 1 {
+            result.push(Subpath { points, closed: false });
+        }
+        result
+    }
+}
+
+// Converts SVG endpoint parametrization to center one and appends the arc points without the starting one
+#[allow(clippy::too_many_arguments)]
+fn arc_points(from: &Point2d, radius_x: f32, radius_y: f32, rotation: f32, large_arc: bool, sweep: bool, to: &Point2d,
+              result: &mut Vec<Point2d>) {
+    let mut radius_x = radius_x.abs();
+    let mut radius_y = radius_y.abs();
+    if from.x == to.x && from.y == to.y {
+        return;
+    }
+    if radius_x == 0.0 || radius_y == 0.0 {
+        result.push(*to);
+        return;
+    }
+    let (sin, cos) = rotation.sin_cos();
+    let half_dx = (from.x - to.x) / 2.0;
+    let half_dy = (from.y - to.y) / 2.0;
+    let x1 = cos * half_dx + sin * half_dy;
+    let y1 = -sin * half_dx + cos * half_dy;
+    // Radii too small to reach the end point are scaled up uniformly
+    let lambda = (x1 * x1) / (radius_x * radius_x) + (y1 * y1) / (radius_y * radius_y);
+    if lambda > 1.0 {
+        radius_x *= lambda.sqrt();
+        radius_y *= lambda.sqrt();
+    }
+    let (rx2, ry2) = (radius_x * radius_x, radius_y * radius_y);
+    let numerator = rx2 * ry2 - rx2 * y1 * y1 - ry2 * x1 * x1;
+    let denominator = rx2 * y1 * y1 + ry2 * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let center_x1 = coefficient * radius_x * y1 / radius_y;
+    let center_y1 = -coefficient * radius_y * x1 / radius_x;
+    let center = Point2d::new(cos * center_x1 - sin * center_y1 + (from.x + to.x) / 2.0,
+                              sin * center_x1 + cos * center_y1 + (from.y + to.y) / 2.0);
+    let start_angle = ((y1 - center_y1) / radius_y).atan2((x1 - center_x1) / radius_x);
+    let end_angle = ((-y1 - center_y1) / radius_y).atan2((-x1 - center_x1) / radius_x);
+    let mut sweep_angle = (end_angle - start_angle).rem_euclid(2.0 * PI);
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    }
+    let arc = Arc::new(PixelColor::black(), center, radius_x, radius_y, start_angle, sweep_angle).with_rotation(rotation);
+    let points = arc.points();
+    result.extend_from_slice(&points[1..(points.len() - 1)]);
+    result.push(*to);
+}
+
+impl Draw for Path {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let subpaths = self.subpaths();
+        if self.filled {
+            let rings: Vec<Vec<Point2d>> = subpaths.into_iter().map(|subpath| subpath.points).collect();
+            let paint = self.paint.clone().unwrap_or(Paint::Solid(self.color));
+            fill_rings(canvas, &rings, self.fill_rule, self.mode, &paint, &self.compositing);
+        } else {
+            for subpath in subpaths.into_iter() {
+                let polyline = if subpath.closed {
+                    Polyline::closed(self.color, subpath.points)
+                } else {
+                    Polyline::new(self.color, subpath.points)
+                };
+                let polyline = polyline.with_mode(self.mode).with_style(self.style).with_compositing(self.compositing);
+                let polyline = match &self.paint {
+                    Some(paint) => polyline.with_paint(paint.clone()),
+                    None => polyline
+                };
+                match self.stroke {
+                    Some(stroke) => polyline.with_stroke(stroke).draw(canvas),
+                    None => polyline.draw(canvas)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::f32::consts::PI;
+use crate::compositing::Compositing;
+use crate::line_style::LineStyle;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::path::Path;
+use crate::polygon::FillRule;
+use crate::stroke::Stroke;
+use crate::golden_tests::assert_golden;
+
+pub(crate) fn star(color: PixelColor, center_x: f32, center_y: f32, radius: f32) -> Path {
+    let point = |i: usize| {
+        let angle = PI / 2.0 + i as f32 * 4.0 * PI / 5.0;
+        Point2d::new(center_x + radius * angle.cos(), center_y + radius * angle.sin())
+    };
+    (1..5).fold(Path::new(color).move_to(point(0)), |path, i| path.line_to(point(i))).close()
+}
+
+fn square(path: Path, x: f32, y: f32, size: f32, counter_clockwise: bool) -> Path {
+    let corners = [Point2d::new(x, y), Point2d::new(x + size, y), Point2d::new(x + size, y + size), Point2d::new(x, y + size)];
+    let ordered: Vec<Point2d> = if counter_clockwise { corners.to_vec() } else { corners.iter().rev().cloned().collect() };
+    ordered[1..].iter().fold(path.move_to(ordered[0]), |path, point| path.line_to(*point)).close()
+}
+
+#[test]
+fn paths_fill_rules() {
+    let nested = |color: PixelColor, x: f32, inner_counter_clockwise: bool| {
+        square(square(Path::new(color), x, 36.0, 24.0, true), x + 6.0, 42.0, 12.0, inner_counter_clockwise)
+    };
+    assert_golden("paths_fill_rules", vec![
+        Box::new(star(PixelColor::red(), 16.0, 16.0, 14.0).with_fill(true).with_fill_rule(FillRule::EvenOdd)),
+        Box::new(star(PixelColor::rgb(0, 255, 0), 48.0, 16.0, 14.0).with_fill(true).with_fill_rule(FillRule::NonZero)),
+        Box::new(nested(PixelColor::rgb(0, 0, 255), 4.0, true).with_fill(true).with_fill_rule(FillRule::NonZero)),
+        Box::new(nested(PixelColor::rgb(255, 255, 0), 36.0, false).with_fill(true).with_fill_rule(FillRule::NonZero))
+    ]);
+}
+
+#[test]
+fn paths_curves_and_arcs() {
+    let shape = Path::new(PixelColor::rgb(0, 255, 255))
+        .move_to(Point2d::new(4.0, 4.0))
+        .line_to(Point2d::new(28.0, 4.0))
+        .quad_to(Point2d::new(40.0, 16.0), Point2d::new(28.0, 28.0))
+        .cubic_to(Point2d::new(20.0, 36.0), Point2d::new(12.0, 20.0), Point2d::new(4.0, 28.0))
+        .close();
+    let gauge = Path::new(PixelColor::rgb(255, 0, 255))
+        .move_to(Point2d::new(36.0, 40.0))
+        .arc_to(12.0, 12.0, 0.0, false, false, Point2d::new(60.0, 40.0))
+        .line_to(Point2d::new(48.0, 40.0))
+        .close();
+    let open = Path::new(PixelColor::rgb(255, 255, 0))
+        .move_to(Point2d::new(4.0, 60.0))
+        .arc_to(16.0, 8.0, 0.3, true, true, Point2d::new(30.0, 54.0))
+        .move_to(Point2d::new(36.0, 12.0))
+        .line_to(Point2d::new(60.0, 30.0));
+    assert_golden("paths_curves_and_arcs", vec![
+        Box::new(shape.with_fill(true)),
+        Box::new(gauge.with_fill(true)),
+        Box::new(open.with_stroke(Stroke::new(2.0))),
+        Box::new(shape.with_style(LineStyle::dashed(&[3.0, 2.0], 0.0).unwrap()).with_compositing(Compositing::source_over()))
+    ]);
+}
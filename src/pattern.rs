@@ -0,0 +1,124 @@
+use std::rc::Rc;
+use crate::canvas::Canvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::transform::AffineTransform;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Sampling {
+    Nearest,
+    Bilinear
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum WrapMode {
+    Repeat,
+    Clamp,
+    Mirror
+}
+
+impl WrapMode {
+    fn apply(&self, value: i32, size: i32) -> i32 {
+        match self {
+            WrapMode::Repeat => value.rem_euclid(size),
+            WrapMode::Clamp => value.clamp(0, size - 1),
+            WrapMode::Mirror => {
+                let value = value.rem_euclid(2 * size);
+                if value >= size { 2 * size - 1 - value } else { value }
+            }
+        }
+    }
+}
+
+// Pixels are stored by rows starting with y = 0, same as the canvas coordinates
+#[derive(Clone, PartialEq, Debug)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<PixelColor>
+}
+
+impl Texture {
+    pub fn new(width: u32, height: u32, pixels: Vec<PixelColor>) -> Texture {
+        assert!(width > 0 && height > 0, "Texture has to have at least one pixel");
+        assert_eq!(pixels.len(), (width * height) as usize, "Pixel count does not match texture size");
+        Texture { width, height, pixels }
+    }
+
+    pub fn from_canvas(canvas: &dyn Canvas) -> Texture {
+        let pixels = (0..canvas.height() as i32)
+            .flat_map(|y| (0..canvas.width() as i32).map(move |x| (x, y)))
+            .map(|(x, y)| canvas.get_pixel(x, y).unwrap())
+            .collect();
+        Texture::new(canvas.width(), canvas.height(), pixels)
+    }
+
+    fn texel(&self, x: i32, y: i32, wrap: WrapMode) -> PixelColor {
+        let x = wrap.apply(x, self.width as i32);
+        let y = wrap.apply(y, self.height as i32);
+        self.pixels[(y as u32 * self.width + x as u32) as usize]
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Pattern {
+    // Shared, so cloning shapes does not copy the pixels
+    pub texture: Rc<Texture>,
+    // Maps texture coordinates to canvas coordinates, set by with_transform together with its inverse
+    transform: AffineTransform,
+    // Maps canvas coordinates back to the texture, computed once instead of for every pixel
+    inverse: AffineTransform,
+    pub sampling: Sampling,
+    pub wrap: WrapMode
+}
+
+impl Pattern {
+    pub fn new(texture: Rc<Texture>) -> Pattern {
+        Pattern { texture, transform: AffineTransform::identity(), inverse: AffineTransform::identity(),
+                  sampling: Sampling::Nearest, wrap: WrapMode::Repeat }
+    }
+
+    // Degenerate transform collapses the whole texture into its first pixel, every canvas point is mapped
+    // to the center of that pixel, which both samplings return unchanged
+    pub fn with_transform(&self, transform: AffineTransform) -> Pattern {
+        let inverse = transform.inverse().unwrap_or(AffineTransform::new(0.0, 0.0, 0.0, 0.0, 0.5, 0.5));
+        Pattern { transform, inverse, ..self.clone() }
+    }
+
+    pub fn transform(&self) -> AffineTransform {
+        self.transform
+    }
+
+    pub fn with_sampling(&self, sampling: Sampling) -> Pattern {
+        Pattern { sampling, ..self.clone() }
+    }
+
+    pub fn with_wrap(&self, wrap: WrapMode) -> Pattern {
+        Pattern { wrap, ..self.clone() }
+    }
+
+    pub fn color_at(&self, x: f32, y: f32) -> PixelColor {
+        let position = self.inverse.apply(&Point2d::new(x, y));
+        match self.sampling {
+            Sampling::Nearest => self.texture.texel(position.x.floor() as i32, position.y.floor() as i32, self.wrap),
+            Sampling::Bilinear => {
+                // Texel centers lie in the middle of the pixels
+                let (u, v) = (position.x - 0.5, position.y - 0.5);
+                let (x0, y0) = (u.floor() as i32, v.floor() as i32);
+                let (ratio_x, ratio_y) = (u - u.floor(), v - v.floor());
+                let bottom = interpolate(&self.texture.texel(x0, y0, self.wrap),
+                                         &self.texture.texel(x0 + 1, y0, self.wrap), ratio_x);
+                let top = interpolate(&self.texture.texel(x0, y0 + 1, self.wrap),
+                                      &self.texture.texel(x0 + 1, y0 + 1, self.wrap), ratio_x);
+                let mix = |a: f32, b: f32| (a + (b - a) * ratio_y).round().clamp(0.0, 255.0) as u8;
+                PixelColor::new(mix(bottom[0], top[0]), mix(bottom[1], top[1]), mix(bottom[2], top[2]),
+                                mix(bottom[3], top[3]))
+            }
+        }
+    }
+}
+
+fn interpolate(from: &PixelColor, to: &PixelColor, ratio: f32) -> [f32; 4] {
+    let channel = |a: u8, b: u8| a as f32 + (b as f32 - a as f32) * ratio;
+    [channel(from.red, to.red), channel(from.green, to.green), channel(from.blue, to.blue), channel(from.alpha, to.alpha)]
+}
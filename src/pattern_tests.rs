@@ -0,0 +1,49 @@
+use std::rc::Rc;
+use crate::clipping::sutherland_hodgman_polygon_clip;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::paint::Paint;
+use crate::pattern::{Pattern, Sampling, Texture, WrapMode};
+use crate::polygon::Polygon;
+use crate::transform::AffineTransform;
+use crate::clipping_tests::clipping_square;
+use crate::golden_tests::assert_golden;
+
+#[test]
+fn pattern_fills() {
+    let checker = Rc::new(Texture::new(2, 2, vec![PixelColor::rgb(255, 255, 255), PixelColor::rgb(0, 0, 255),
+                                                  PixelColor::rgb(0, 0, 255), PixelColor::rgb(255, 255, 255)]));
+    let gradient = Rc::new(Texture::new(3, 1, vec![PixelColor::red(), PixelColor::rgb(0, 255, 0), PixelColor::rgb(0, 0, 255)]));
+    let hatching = Rc::new(Texture::new(6, 6, (0..36).map(|i| {
+        if (i % 6 + i / 6) % 6 == 0 { PixelColor::rgb(255, 255, 0) } else { PixelColor::black().with_alpha(0) }
+    }).collect()));
+    let rectangle = |x: f32, y: f32| vec![Point2d::new(x, y), Point2d::new(x + 28.0, y), Point2d::new(x + 28.0, y + 28.0),
+                                           Point2d::new(x, y + 28.0)];
+    let tiles = Pattern::new(checker.clone()).with_transform(AffineTransform::scale(4.0, 4.0));
+    let rotated = Pattern::new(checker.clone())
+        .with_transform(AffineTransform::scale(5.0, 5.0).then(&AffineTransform::rotation(0.5))
+            .then(&AffineTransform::translation(48.0, 16.0)))
+        .with_sampling(Sampling::Bilinear);
+    let clamped = Pattern::new(gradient.clone())
+        .with_transform(AffineTransform::scale(6.0, 6.0).then(&AffineTransform::translation(8.0, 0.0)))
+        .with_sampling(Sampling::Bilinear)
+        .with_wrap(WrapMode::Clamp);
+    let mirrored = Pattern::new(gradient)
+        .with_transform(AffineTransform::scale(3.0, 1.0).then(&AffineTransform::skew(0.6, 0.0)))
+        .with_wrap(WrapMode::Mirror);
+    let clipped = sutherland_hodgman_polygon_clip(&Polygon::new(PixelColor::black(), rectangle(36.0, 36.0)),
+                                                  &clipping_square()).unwrap();
+    assert_golden("pattern_fills", vec![
+        Box::new(Polygon::new(PixelColor::black(), rectangle(2.0, 2.0)).with_paint(Paint::Pattern(tiles))),
+        Box::new(Polygon::new(PixelColor::black(), rectangle(34.0, 2.0)).with_paint(Paint::Pattern(rotated))),
+        Box::new(Polygon::new(PixelColor::black(), rectangle(2.0, 34.0)).with_paint(Paint::Pattern(clamped))),
+        Box::new(Polygon::new(PixelColor::black(), rectangle(34.0, 34.0)).with_paint(Paint::Pattern(mirrored))),
+        Box::new(clipped.with_paint(Paint::Pattern(Pattern::new(hatching))))
+    ]);
+    // Degenerate transform shows the first texel everywhere, whatever the sampling
+    for sampling in [Sampling::Nearest, Sampling::Bilinear].iter() {
+        let collapsed = Pattern::new(checker.clone()).with_transform(AffineTransform::scale(0.0, 3.0)).with_sampling(*sampling);
+        assert_eq!(collapsed.color_at(13.5, 7.5), PixelColor::rgb(255, 255, 255));
+        assert_eq!(collapsed.color_at(-40.5, 2.5), PixelColor::rgb(255, 255, 255));
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct PixelColor {
     pub red: u8,
     pub green: u8,
@@ -7,11 +7,23 @@ pub struct PixelColor {
 }
 
 impl PixelColor {
+    pub fn new(red: u8, green: u8, blue: u8, alpha: u8) -> PixelColor {
+        PixelColor { red, green, blue, alpha }
+    }
+
+    pub fn rgb(red: u8, green: u8, blue: u8) -> PixelColor {
+        PixelColor::new(red, green, blue, 255)
+    }
+
     pub fn red() -> PixelColor {
-        PixelColor { red: 255, green: 0, blue: 0, alpha: 0 }
+        PixelColor::rgb(255, 0, 0)
     }
 
     pub fn black() -> PixelColor {
-        PixelColor { red: 0, green: 0, blue: 0, alpha: 0 }
+        PixelColor::rgb(0, 0, 0)
     }
-}
\ No newline at end of file
+
+    pub fn with_alpha(&self, alpha: u8) -> PixelColor {
+        PixelColor { alpha, ..*self }
+    }
+}
@@ -1,8 +1,8 @@
 use crate::draw::Draw;
 use crate::pixel_color::PixelColor;
-use crate::raw_canvas::RawCanvas;
+use crate::canvas::Canvas;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Point2d {
     pub x: f32,
     pub y: f32
@@ -19,9 +19,7 @@ impl Point2d {
 }
 
 impl Draw for Point2d {
-    fn draw(&self, canvas: &RawCanvas) {
-        let x = self.x as u32;
-        let y = self.y as u32;
-        canvas.put_pixel(x, y, &PixelColor::red());
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        canvas.put_pixel_f(self.x, self.y, &PixelColor::red());
     }
 }
\ No newline at end of file
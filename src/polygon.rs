@@ -1,85 +1,253 @@
-use std::collections::{HashMap, HashSet};
-use std::iter::Map;
+use std::collections::HashMap;
 use crate::draw::Draw;
 use crate::pixel_color::PixelColor;
 use crate::point2d::Point2d;
-use crate::raw_canvas::RawCanvas;
+use crate::polyline::Polyline;
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::line::LineMode;
+use crate::paint::Paint;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero
+}
+
+impl FillRule {
+    pub fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Polygon {
     pub points: Vec<Point2d>,
-    pub color: PixelColor
+    // Have to be oriented opposite to the outline to stay empty with the non-zero fill rule
+    pub holes: Vec<Vec<Point2d>>,
+    pub color: PixelColor,
+    pub compositing: Compositing,
+    pub fill_rule: FillRule,
+    pub mode: LineMode,
+    // Overrides color of the fill when set
+    pub paint: Option<Paint>
 }
 
 impl Polygon {
     pub fn new(color: PixelColor, points: Vec<Point2d>) -> Polygon {
-        Polygon { color, points }
+        Polygon { color, points, holes: Vec::new(), compositing: Compositing::source_over(),
+                  fill_rule: FillRule::EvenOdd, mode: LineMode::Aliased, paint: None }
+    }
+
+    pub fn with_holes(&self, holes: Vec<Vec<Point2d>>) -> Polygon {
+        Polygon { holes, ..self.clone() }
+    }
+
+    pub fn with_fill_rule(&self, fill_rule: FillRule) -> Polygon {
+        Polygon { fill_rule, ..self.clone() }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Polygon {
+        Polygon { compositing, ..self.clone() }
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> Polygon {
+        Polygon { mode, ..self.clone() }
+    }
+
+    pub fn with_paint(&self, paint: Paint) -> Polygon {
+        Polygon { paint: Some(paint), ..self.clone() }
+    }
+
+    // Uses the fill rule, so points covered by holes are outside
+    pub fn contains(&self, point: &Point2d) -> bool {
+        let winding: i32 = std::iter::once(&self.points).chain(self.holes.iter())
+            .map(|ring| winding_number(ring, point))
+            .sum();
+        self.fill_rule.is_inside(winding)
+    }
+
+    pub fn outline(&self) -> Polyline {
+        Polyline::closed(self.color, self.points.clone()).with_compositing(self.compositing).with_mode(self.mode)
+    }
+}
+
+fn winding_number(ring: &[Point2d], point: &Point2d) -> i32 {
+    let length = ring.len();
+    let mut winding = 0;
+    for i in 0..length {
+        let (a, b) = (&ring[i], &ring[(i + 1) % length]);
+        let side = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+        if a.y <= point.y && b.y > point.y && side > 0.0 {
+            winding += 1;
+        } else if b.y <= point.y && a.y > point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+// Vertices are converted to fixed point numbers with 16 fractional bits
+const FIXED_SHIFT: u32 = 16;
+const FIXED_ONE: i64 = 1 << FIXED_SHIFT;
+const FIXED_HALF: i64 = FIXED_ONE / 2;
+
+fn to_fixed(value: f64) -> i64 {
+    (value * FIXED_ONE as f64).round() as i64
+}
+
+// Coordinates are kept within this distance of the origin, far beyond any canvas
+const GUARD_BAND: f64 = (1 << 24) as f64;
+
+// Parts of the edge outside the guard band are moved onto its border. Pieces on the left and right borders
+// are vertical and keep the winding of every point inside, pieces on the top and bottom are horizontal and
+// never sampled.
+fn guarded_edge(first: &Point2d, second: &Point2d) -> Vec<((f64, f64), (f64, f64))> {
+    let (a, b) = ((first.x as f64, first.y as f64), (second.x as f64, second.y as f64));
+    let mut cuts = vec![0.0, 1.0];
+    for (from, to) in [(a.0, b.0), (a.1, b.1)].iter() {
+        for border in [-GUARD_BAND, GUARD_BAND].iter() {
+            if (from < border) != (to < border) {
+                cuts.push((border - from) / (to - from));
+            }
+        }
     }
+    cuts.sort_by(|a, b| a.total_cmp(b));
+    let point = |t: f64| {
+        let (x, y) = if t == 1.0 { b } else { (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t) };
+        (x.clamp(-GUARD_BAND, GUARD_BAND), y.clamp(-GUARD_BAND, GUARD_BAND))
+    };
+    cuts.windows(2).map(|pair| (point(pair[0]), point(pair[1]))).collect()
 }
 
 #[derive(Copy, Clone)]
 struct Fraction {
-    nominator: i32,
-    denominator: i32
+    nominator: i64,
+    denominator: i64
 }
 
+// Fixed point value with exact remainder, nominator is kept in range [0, denominator)
 #[derive(Copy, Clone)]
 struct LowerEndpointInc {
-    increment: i32,
+    increment: i64,
     fraction: Fraction
 }
 
+impl LowerEndpointInc {
+    fn from_division(base: i64, nominator: i128, denominator: i64) -> LowerEndpointInc {
+        LowerEndpointInc {
+            increment: base + nominator.div_euclid(denominator as i128) as i64,
+            fraction: Fraction { nominator: nominator.rem_euclid(denominator as i128) as i64, denominator }
+        }
+    }
+
+    fn add(&mut self, other: &LowerEndpointInc) {
+        self.increment += other.increment;
+        self.fraction.nominator += other.fraction.nominator;
+        if self.fraction.nominator >= self.fraction.denominator {
+            self.fraction.nominator -= self.fraction.denominator;
+            self.increment += 1;
+        }
+    }
+
+    // Adds the other value count times at once
+    fn add_times(&mut self, other: &LowerEndpointInc, count: i64) {
+        let nominator = self.fraction.nominator as i128 + other.fraction.nominator as i128 * count as i128;
+        let denominator = self.fraction.denominator as i128;
+        self.increment = (self.increment as i128 + other.increment as i128 * count as i128
+            + nominator.div_euclid(denominator)) as i64;
+        self.fraction.nominator = nominator.rem_euclid(denominator) as i64;
+    }
+
+    fn value(&self) -> f64 {
+        self.increment as f64 + self.fraction.nominator as f64 / self.fraction.denominator as f64
+    }
+
+    // First pixel whose center is not on the left of this value
+    fn first_pixel_right(&self) -> i32 {
+        let shifted = self.increment - FIXED_HALF;
+        let pixel = shifted.div_euclid(FIXED_ONE);
+        if shifted.rem_euclid(FIXED_ONE) == 0 && self.fraction.nominator == 0 {
+            pixel as i32
+        } else {
+            (pixel + 1) as i32
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 struct SegmentDesc {
-    y_max: u32,
+    // Last row whose pixel centers the edge crosses
+    y_max: i32,
+    // Fixed point x of the crossing with the current row center
     lower_endpoint_x: LowerEndpointInc,
-    slope_inv: Fraction
+    // Change of x between two rows
+    slope_inv: LowerEndpointInc,
+    // 1 for edges going up from the first to the second point, -1 for edges going down
+    direction: i32
 }
 
 struct EdgeTable {
-    rows: HashMap<u32, Vec<SegmentDesc>>
+    rows: HashMap<i32, Vec<SegmentDesc>>,
+    y_min: i32,
+    y_max: i32
 }
 
 impl EdgeTable {
-    fn from_points(points: &Vec<Point2d>) -> EdgeTable {
-        let mut rows: HashMap<u32, Vec<SegmentDesc>> = HashMap::new();
-        let length = points.len();
-        if length >= 2 {
+    // Edges are sampled at pixel centers. A center lying exactly on the lower endpoint belongs to the edge below,
+    // so rows shared by adjacent polygons are filled only once. Edges starting below the first row are advanced
+    // to it, so rows which are never visited cost nothing.
+    fn from_rings(rings: &[Vec<Point2d>], first_visible_row: i32) -> EdgeTable {
+        let mut rows: HashMap<i32, Vec<SegmentDesc>> = HashMap::new();
+        let mut y_min = i32::MAX;
+        let mut y_max = i32::MIN;
+        for points in rings.iter() {
+            let length = points.len();
+            if length < 2 {
+                continue;
+            }
             for x in 0..length {
-                let fst_idx = x;
-                let mut snd_idx = x + 1;
-                if x == length - 1 {
-                    snd_idx = 0;
+                let (first_point, second_point) = (&points[x], &points[(x + 1) % length]);
+                for ((first_x, first_y), (second_x, second_y)) in guarded_edge(first_point, second_point).into_iter() {
+                    let (first_x, first_y) = (to_fixed(first_x), to_fixed(first_y));
+                    let (second_x, second_y) = (to_fixed(second_x), to_fixed(second_y));
+                    if first_y == second_y {
+                        continue;
+                    }
+                    let direction = if first_y < second_y { 1 } else { -1 };
+                    let ((lower_x, lower_y), (upper_x, upper_y)) = if first_y < second_y {
+                        ((first_x, first_y), (second_x, second_y))
+                    } else {
+                        ((second_x, second_y), (first_x, first_y))
+                    };
+                    let first_row = (lower_y - FIXED_HALF).div_euclid(FIXED_ONE) + 1;
+                    let last_row = (upper_y - FIXED_HALF).div_euclid(FIXED_ONE).min(i32::MAX as i64);
+                    if first_row > last_row || last_row < first_visible_row as i64 {
+                        continue;
+                    }
+                    let delta_x = upper_x - lower_x;
+                    let delta_y = upper_y - lower_y;
+                    let first_center = first_row * FIXED_ONE + FIXED_HALF;
+                    let mut lower_endpoint_x = LowerEndpointInc::from_division(lower_x, (first_center - lower_y) as i128 * delta_x as i128,
+                                                                               delta_y);
+                    let slope_inv = LowerEndpointInc::from_division(0, FIXED_ONE as i128 * delta_x as i128, delta_y);
+                    let first_row = if first_row < first_visible_row as i64 {
+                        lower_endpoint_x.add_times(&slope_inv, first_visible_row as i64 - first_row);
+                        first_visible_row as i64
+                    } else {
+                        first_row
+                    };
+                    let desc = SegmentDesc { y_max: last_row as i32, lower_endpoint_x, slope_inv, direction };
+                    rows.entry(first_row as i32).or_default().push(desc);
+                    y_min = y_min.min(first_row as i32);
+                    y_max = y_max.max(last_row as i32);
                 }
-                let first_point = points.get(fst_idx).unwrap();
-                let second_point = points.get(snd_idx).unwrap();
-                let smaller_x = (if first_point.x < second_point.x { first_point.x } else { second_point.x }).round() as u32;
-                let bigger_x = (if first_point.x > second_point.x { first_point.x } else { second_point.x }).round() as u32;
-                let smaller_y = (if first_point.y < second_point.y { first_point.y } else { second_point.y }).round() as u32;
-                let bigger_y = (if first_point.y > second_point.y { first_point.y } else { second_point.y }).round() as u32;
-                let farthest_y = (if first_point.x > second_point.x { first_point.y } else { second_point.y }).round() as u32;
-                let nearest_y = (if first_point.x < second_point.x { first_point.y } else { second_point.y }).round() as u32;
-                let delta_x = bigger_x as i32 - smaller_x as i32;
-                let delta_y = farthest_y as i32 - nearest_y as i32;
-                let nominator = if delta_x < 0 && delta_y < 0 {
-                    delta_x.abs()
-                } else if delta_y < 0 {
-                    -delta_x
-                } else {
-                    delta_x
-                };
-                let denominator = delta_y.abs();
-                let lower_endpoint_x = (if first_point.y < second_point.y { first_point.x } else { second_point.x }).round() as i32;
-                let lower_endpoint_inc = LowerEndpointInc { increment: lower_endpoint_x, fraction: Fraction {nominator: 0, denominator} };
-                let desc = SegmentDesc { y_max: bigger_y, lower_endpoint_x: lower_endpoint_inc, slope_inv: Fraction {nominator, denominator } };
-                let row = rows.entry(smaller_y).or_insert(Vec::new());
-                row.push(desc);
             }
         }
-        for (_, val) in rows.iter_mut() {
-            val.sort_by(|a, b|  a.lower_endpoint_x.increment.cmp(&b.lower_endpoint_x.increment));
-        }
-        EdgeTable { rows }
+        EdgeTable { rows, y_min, y_max }
     }
 }
 
@@ -92,75 +260,155 @@ impl ActiveEdgeTable {
         ActiveEdgeTable { edges: Vec::new() }
     }
 
-    fn insert_row(&mut self, y: u32, edge_table: &EdgeTable) {
-        match edge_table.rows.get(&y) {
-            None => {}
-            Some(row) => {
-                for entry in row.iter() {
-                    self.edges.push(entry.clone());
-                }
-                self.edges.sort_by(|a, b|  a.lower_endpoint_x.increment.cmp(&b.lower_endpoint_x.increment));
-            }
+    fn insert_row(&mut self, y: i32, edge_table: &EdgeTable) {
+        if let Some(row) = edge_table.rows.get(&y) {
+            self.edges.extend_from_slice(row);
+            self.sort();
         }
     }
 
-    fn remove_lover_edges(&mut self, row: u32) {
-        let mut idx_to_remove: HashSet<usize> = HashSet::new();
-        let length = self.edges.len();
-        for x in 0..length {
-            if self.edges.get(x).unwrap().y_max < row {
-                idx_to_remove.insert(x);
+    fn sort(&mut self) {
+        self.edges.sort_by(|a, b| a.lower_endpoint_x.value().total_cmp(&b.lower_endpoint_x.value()));
+    }
+
+    fn remove_lover_edges(&mut self, row: i32) {
+        self.edges.retain(|edge| edge.y_max >= row);
+    }
+
+    fn increment_row(&mut self) {
+        for entry in self.edges.iter_mut() {
+            let step = entry.slope_inv;
+            entry.lower_endpoint_x.add(&step);
+        }
+        // Edges of self intersecting outlines swap their order
+        self.sort();
+    }
+
+    // Pixel ranges, from inclusive and to exclusive, lying inside according to the fill rule.
+    // Pixel center on the left edge of a span is filled, on the right one it is not.
+    fn spans(&self, fill_rule: FillRule) -> Vec<(i32, i32)> {
+        let mut result = Vec::new();
+        let mut winding = 0;
+        for pair in self.edges.windows(2) {
+            winding += pair[0].direction;
+            if fill_rule.is_inside(winding) {
+                result.push((pair[0].lower_endpoint_x.first_pixel_right(), pair[1].lower_endpoint_x.first_pixel_right()));
             }
         }
-        let mut new_edges = Vec::new();
-        for x in 0..length {
-            if !idx_to_remove.contains(&x) {
-                new_edges.push(self.edges.get(x).unwrap().clone());
+        result
+    }
+
+    // Exact span boundaries in pixel units, used to compute horizontal coverage
+    fn exact_spans(&self, fill_rule: FillRule) -> Vec<(f64, f64)> {
+        let mut result = Vec::new();
+        let mut winding = 0;
+        for pair in self.edges.windows(2) {
+            winding += pair[0].direction;
+            if fill_rule.is_inside(winding) {
+                result.push((pair[0].lower_endpoint_x.value() / FIXED_ONE as f64,
+                             pair[1].lower_endpoint_x.value() / FIXED_ONE as f64));
             }
         }
-        self.edges = new_edges;
+        result
     }
+}
 
-    fn increment_row(&mut self) {
-        for entry in self.edges.iter_mut() {
-            let mut nominator = entry.lower_endpoint_x.fraction.nominator + entry.slope_inv.nominator;
-            let denominator = entry.lower_endpoint_x.fraction.denominator;
-            if denominator != 0 {
-                entry.lower_endpoint_x.increment += nominator / denominator;
-                if nominator.abs() > denominator {
-                    let count = (nominator / denominator).abs();
-                    if nominator < denominator {
-                        nominator += count * denominator;
-                    } else {
-                        nominator -= count * denominator;
-                    }
+fn scan_rows(rings: &[Vec<Point2d>], last_row: i32, mut visit: impl FnMut(i32, &ActiveEdgeTable)) {
+    let finite = rings.iter().flat_map(|ring| ring.iter()).all(|p| p.x.is_finite() && p.y.is_finite());
+    if !finite {
+        return;
+    }
+    let edge_table = EdgeTable::from_rings(rings, 0);
+    let mut active_edge_table = ActiveEdgeTable::new();
+    let last_row = edge_table.y_max.min(last_row);
+    let mut row = edge_table.y_min.max(0);
+    while row <= last_row {
+        active_edge_table.remove_lover_edges(row);
+        active_edge_table.insert_row(row, &edge_table);
+        visit(row, &active_edge_table);
+        active_edge_table.increment_row();
+        row += 1;
+    }
+}
+
+// Scanline fill of area bounded by any number of closed rings
+pub fn fill_rings(canvas: &mut dyn Canvas, rings: &[Vec<Point2d>], fill_rule: FillRule, mode: LineMode,
+                  paint: &Paint, compositing: &Compositing) {
+    match mode {
+        LineMode::Aliased => {
+            let last_row = canvas.height() as i32 - 1;
+            scan_rows(rings, last_row, |row, active_edge_table| {
+                for (from, to) in active_edge_table.spans(fill_rule).iter() {
+                    paint.fill_span(canvas, row, *from, *to - 1, compositing);
                 }
+            });
+        },
+        LineMode::AntiAliased => fill_rings_anti_aliased(canvas, rings, fill_rule, paint, compositing)
+    }
+}
+
+// Rows are sampled this many times per pixel, horizontal coverage of every sample is exact
+const SUBSAMPLES: i32 = 16;
+
+fn fill_rings_anti_aliased(canvas: &mut dyn Canvas, rings: &[Vec<Point2d>], fill_rule: FillRule, paint: &Paint,
+                           compositing: &Compositing) {
+    let width = canvas.width() as usize;
+    let scaled: Vec<Vec<Point2d>> = rings.iter()
+        .map(|ring| ring.iter().map(|p| Point2d::new(p.x, p.y * SUBSAMPLES as f32)).collect())
+        .collect();
+    let mut coverage = vec![0.0f32; width];
+    let mut current_row = None;
+    let last_row = canvas.height() as i32 * SUBSAMPLES - 1;
+    scan_rows(&scaled, last_row, |sample_row, active_edge_table| {
+        let row = sample_row / SUBSAMPLES;
+        if current_row != Some(row) {
+            if let Some(previous) = current_row {
+                flush_coverage(canvas, previous, &mut coverage, paint, compositing);
             }
-            entry.lower_endpoint_x.fraction.nominator = nominator;
+            current_row = Some(row);
+        }
+        for (from, to) in active_edge_table.exact_spans(fill_rule).iter() {
+            accumulate_span(&mut coverage, *from, *to);
+        }
+    });
+    if let Some(row) = current_row {
+        flush_coverage(canvas, row, &mut coverage, paint, compositing);
+    }
+}
+
+fn accumulate_span(coverage: &mut [f32], from: f64, to: f64) {
+    let from = from.max(0.0);
+    let to = to.min(coverage.len() as f64);
+    if from >= to {
+        return;
+    }
+    let weight = 1.0 / SUBSAMPLES as f64;
+    let first = from.floor() as usize;
+    let last = to.ceil() as usize;
+    for (x, value) in coverage.iter_mut().enumerate().take(last).skip(first) {
+        let overlap = to.min(x as f64 + 1.0) - from.max(x as f64);
+        *value += (overlap * weight) as f32;
+    }
+}
+
+fn flush_coverage(canvas: &mut dyn Canvas, row: i32, coverage: &mut [f32], paint: &Paint, compositing: &Compositing) {
+    for (x, value) in coverage.iter_mut().enumerate() {
+        if *value > 0.0 {
+            paint.composite_pixel(canvas, x as i32, row, value.min(1.0), compositing);
+            *value = 0.0;
         }
     }
 }
 
 impl Draw for Polygon {
-    fn draw(&self, canvas: &RawCanvas) {
-        let edge_table = EdgeTable::from_points(&self.points);
-        let mut active_edge_table = ActiveEdgeTable::new();
-        let smallest_y = self.points.iter().min_by(|a, b| a.y.total_cmp(&b.y)).unwrap().y.round() as u32;
-        let biggest_y = self.points.iter().max_by(|a, b| a.y.total_cmp(&b.y)).unwrap().y.round() as u32;
-        for row in smallest_y..(biggest_y + 1) {
-            active_edge_table.remove_lover_edges(row);
-            active_edge_table.insert_row(row, &edge_table);
-            let length = active_edge_table.edges.len();
-            for x in 0..(length - 1) {
-                let first_edge = active_edge_table.edges.get(x).unwrap();
-                let second_edge = active_edge_table.edges.get(x + 1).unwrap();
-                if x % 2 == 0 {
-                    for x_coord in (first_edge.lower_endpoint_x.increment)..(second_edge.lower_endpoint_x.increment) {
-                        canvas.put_pixel(x_coord as u32, row, &self.color);
-                    }
-                }
-            }
-            active_edge_table.increment_row()
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let paint = self.paint.clone().unwrap_or(Paint::Solid(self.color));
+        if self.holes.is_empty() {
+            fill_rings(canvas, std::slice::from_ref(&self.points), self.fill_rule, self.mode, &paint, &self.compositing);
+        } else {
+            let mut rings = vec![self.points.clone()];
+            rings.extend(self.holes.iter().cloned());
+            fill_rings(canvas, &rings, self.fill_rule, self.mode, &paint, &self.compositing);
         }
     }
-}
\ No newline at end of file
+}
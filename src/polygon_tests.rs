@@ -0,0 +1,142 @@
+use std::f32::consts::PI;
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::line::LineMode;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::path::Path;
+use crate::polygon::{FillRule, Polygon};
+use crate::path_tests::star;
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden, render};
+
+#[test]
+fn polygon_convex() {
+    assert_golden("polygon_convex", vec![
+        Box::new(Polygon::new(PixelColor::red(), vec![Point2d::new(5.0, 5.0), Point2d::new(10.0, 50.0),
+                                                      Point2d::new(55.0, 58.0), Point2d::new(58.0, 8.0)]))
+    ]);
+}
+
+#[test]
+fn polygon_concave() {
+    assert_golden("polygon_concave", vec![
+        Box::new(Polygon::new(PixelColor::red(), vec![Point2d::new(5.0, 5.0), Point2d::new(10.0, 50.0),
+                                                      Point2d::new(25.0, 33.0), Point2d::new(50.0, 62.0),
+                                                      Point2d::new(53.0, 8.0)]))
+    ]);
+}
+
+#[test]
+fn polygon_fill_rules() {
+    let star = |center_x: f32, center_y: f32| -> Vec<Point2d> {
+        (0..5).map(|i| {
+            let angle = PI / 2.0 + i as f32 * 4.0 * PI / 5.0;
+            Point2d::new(center_x + 14.0 * angle.cos(), center_y + 14.0 * angle.sin())
+        }).collect()
+    };
+    // Outline going twice around the inner square overlaps itself
+    let overlapping = |x: f32| -> Vec<Point2d> {
+        vec![Point2d::new(x, 36.0), Point2d::new(x + 24.0, 36.0), Point2d::new(x + 24.0, 60.0), Point2d::new(x + 6.0, 60.0),
+             Point2d::new(x + 6.0, 42.0), Point2d::new(x + 18.0, 42.0), Point2d::new(x + 18.0, 54.0), Point2d::new(x, 54.0)]
+    };
+    assert_golden("polygon_fill_rules", vec![
+        Box::new(Polygon::new(PixelColor::red(), star(16.0, 16.0))),
+        Box::new(Polygon::new(PixelColor::rgb(0, 255, 0), star(48.0, 16.0)).with_fill_rule(FillRule::NonZero)),
+        Box::new(Polygon::new(PixelColor::rgb(0, 0, 255), overlapping(4.0)).with_fill_rule(FillRule::EvenOdd)),
+        Box::new(Polygon::new(PixelColor::rgb(255, 255, 0), overlapping(36.0)).with_fill_rule(FillRule::NonZero))
+    ]);
+}
+
+#[test]
+fn polygon_anti_aliased() {
+    let concave = vec![Point2d::new(3.0, 3.0), Point2d::new(8.0, 30.0), Point2d::new(16.0, 20.0),
+                       Point2d::new(29.5, 30.5), Point2d::new(30.0, 4.0)];
+    assert_golden("polygon_anti_aliased", vec![
+        Box::new(Polygon::new(PixelColor::red(), concave).with_mode(LineMode::AntiAliased)),
+        Box::new(star(PixelColor::rgb(0, 255, 0), 48.0, 16.0, 14.0).with_fill(true).with_fill_rule(FillRule::EvenOdd)
+            .with_mode(LineMode::AntiAliased)),
+        Box::new(Path::new(PixelColor::rgb(0, 255, 255))
+            .move_to(Point2d::new(4.0, 40.0))
+            .cubic_to(Point2d::new(12.0, 70.0), Point2d::new(28.0, 30.0), Point2d::new(28.0, 60.0))
+            .close()
+            .with_fill(true)
+            .with_mode(LineMode::AntiAliased)),
+        Box::new(Polygon::new(PixelColor::rgb(255, 255, 0), vec![Point2d::new(36.0, 36.0), Point2d::new(60.0, 40.0),
+                                                                 Point2d::new(44.0, 60.0)])
+            .with_mode(LineMode::AntiAliased)
+            .with_compositing(Compositing::source_over()))
+    ]);
+}
+
+// Rows below the canvas are skipped without walking them one by one
+#[test]
+fn polygons_far_below_canvas() {
+    let triangle = vec![Point2d::new(0.0, -3e7), Point2d::new(30.0, 30.0), Point2d::new(0.0, 30.0)];
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Polygon::new(PixelColor::red(), triangle.clone()).draw(&mut canvas);
+    for y in 0..HEIGHT as i32 {
+        for x in 0..WIDTH as i32 {
+            let inside = x < 30 && y < 30;
+            assert_eq!(canvas.get_pixel(x, y) == Some(PixelColor::red()), inside, "pixel ({}, {})", x, y);
+        }
+    }
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Polygon::new(PixelColor::red(), triangle).with_mode(LineMode::AntiAliased).draw(&mut canvas);
+    assert_eq!(canvas.get_pixel(0, 0), Some(PixelColor::red()));
+    assert_eq!(canvas.get_pixel(28, 29), Some(PixelColor::red()));
+    assert_eq!(canvas.get_pixel(31, 10), Some(PixelColor::new(0, 0, 0, 0)));
+}
+
+#[test]
+fn polygons_far_beside_canvas() {
+    // Edges reaching far to the sides keep their slope across the canvas
+    for far in [1e10f32, 1e20, f32::MAX].iter() {
+        let triangle = vec![Point2d::new(-far, 0.0), Point2d::new(*far, 0.0), Point2d::new(0.0, 64.0)];
+        for mode in [LineMode::Aliased, LineMode::AntiAliased].iter() {
+            let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+            Polygon::new(PixelColor::red(), triangle.clone()).with_mode(*mode).draw(&mut canvas);
+            assert_eq!(canvas.get_pixel(0, 0), Some(PixelColor::red()), "{} {:?}", far, mode);
+            assert_eq!(canvas.get_pixel(WIDTH as i32 - 1, HEIGHT as i32 - 2), Some(PixelColor::red()), "{} {:?}", far, mode);
+        }
+    }
+    // A sliver crossing the canvas from far left to far right covers exactly the rows between its edges
+    let sliver = vec![Point2d::new(-1e20, 10.0), Point2d::new(1e20, 10.0), Point2d::new(1e20, 20.0), Point2d::new(-1e20, 20.0)];
+    let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+    Polygon::new(PixelColor::red(), sliver).draw(&mut canvas);
+    for y in 0..HEIGHT as i32 {
+        for x in 0..WIDTH as i32 {
+            assert_eq!(canvas.get_pixel(x, y) == Some(PixelColor::red()), (10..20).contains(&y), "pixel ({}, {})", x, y);
+        }
+    }
+}
+
+// Every pixel of the tiled area has to be covered exactly once, gaps stay black and overlaps get brighter
+#[test]
+fn tiled_polygons_share_edges() {
+    let mut drawables: Vec<Box<dyn Draw>> = Vec::new();
+    let tile = PixelColor::rgb(255, 255, 255).with_alpha(128);
+    let grid = |i: usize, j: usize| Point2d::new(3.3 + i as f32 * 9.7 + (j as f32 * 1.3).sin(), 2.6 + j as f32 * 9.4 + (i as f32 * 0.7).cos());
+    for i in 0..6 {
+        for j in 0..6 {
+            drawables.push(Box::new(Polygon::new(tile, vec![grid(i, j), grid(i + 1, j), grid(i + 1, j + 1)])));
+            drawables.push(Box::new(Polygon::new(tile, vec![grid(i, j), grid(i + 1, j + 1), grid(i, j + 1)])));
+        }
+    }
+    let canvas = render(&drawables);
+    let mut boundary: Vec<Point2d> = (0..6).map(|i| grid(i, 0)).collect();
+    boundary.extend((0..6).map(|j| grid(6, j)));
+    boundary.extend((1..7).rev().map(|i| grid(i, 6)));
+    boundary.extend((1..7).rev().map(|j| grid(0, j)));
+    let mut mask = MemoryCanvas::new(WIDTH, HEIGHT);
+    mask.clean_color(&PixelColor::black());
+    Polygon::new(PixelColor::rgb(128, 128, 128), boundary).draw(&mut mask);
+    for y in 0..HEIGHT as i32 {
+        for x in 0..WIDTH as i32 {
+            let expected = mask.get_pixel(x, y).unwrap();
+            let actual = canvas.get_pixel(x, y).unwrap();
+            assert_eq!(expected.red, actual.red, "pixel ({}, {}) covered wrong number of times", x, y);
+        }
+    }
+}
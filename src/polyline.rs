@@ -0,0 +1,92 @@
+use crate::canvas::Canvas;
+use crate::compositing::Compositing;
+use crate::draw::Draw;
+use crate::line::{Line, LineMode};
+use crate::line_style::LineStyle;
+use crate::paint::Paint;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::{FillRule, fill_rings};
+use crate::stroke::Stroke;
+
+#[derive(Clone)]
+pub struct Polyline {
+    pub points: Vec<Point2d>,
+    pub closed: bool,
+    pub color: PixelColor,
+    pub compositing: Compositing,
+    pub stroke: Option<Stroke>,
+    pub style: LineStyle,
+    pub mode: LineMode,
+    // Overrides color of the thick stroke when set
+    pub paint: Option<Paint>
+}
+
+impl Polyline {
+    pub fn new(color: PixelColor, points: Vec<Point2d>) -> Polyline {
+        Polyline { points, closed: false, color, compositing: Compositing::source_over(), stroke: None,
+                   style: LineStyle::Solid, mode: LineMode::Aliased, paint: None }
+    }
+
+    pub fn closed(color: PixelColor, points: Vec<Point2d>) -> Polyline {
+        Polyline { closed: true, ..Polyline::new(color, points) }
+    }
+
+    pub fn with_stroke(&self, stroke: Stroke) -> Polyline {
+        Polyline { stroke: Some(stroke), ..self.clone() }
+    }
+
+    pub fn with_compositing(&self, compositing: Compositing) -> Polyline {
+        Polyline { compositing, ..self.clone() }
+    }
+
+    pub fn with_style(&self, style: LineStyle) -> Polyline {
+        Polyline { style, ..self.clone() }
+    }
+
+    pub fn with_mode(&self, mode: LineMode) -> Polyline {
+        Polyline { mode, ..self.clone() }
+    }
+
+    pub fn with_paint(&self, paint: Paint) -> Polyline {
+        Polyline { paint: Some(paint), ..self.clone() }
+    }
+
+    pub fn lines(&self) -> Vec<Line> {
+        let length = self.points.len();
+        let count = if self.closed && length > 2 { length } else { length.saturating_sub(1) };
+        (0..count)
+            .map(|i| {
+                let first = self.points[i];
+                let last = self.points[(i + 1) % length];
+                Line::with_color(self.color, first.x, first.y, last.x, last.y)
+                    .with_compositing(self.compositing)
+                    .with_style(self.style)
+                    .with_mode(self.mode)
+            })
+            .collect()
+    }
+}
+
+impl Draw for Polyline {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        match self.stroke {
+            None => {
+                let mut phase = 0.0;
+                for line in self.lines().iter() {
+                    phase = line.draw_with_phase(canvas, phase);
+                }
+            }
+            Some(stroke) => {
+                // Pieces overlap at joins and caps, a single fill composites every pixel once
+                let rings: Vec<Vec<Point2d>> = stroke
+                    .stroke_styled_polyline(&self.points, self.closed, &self.style, self.color, self.compositing)
+                    .into_iter()
+                    .map(|polygon| polygon.points)
+                    .collect();
+                let paint = self.paint.clone().unwrap_or(Paint::Solid(self.color));
+                fill_rings(canvas, &rings, FillRule::NonZero, LineMode::Aliased, &paint, &self.compositing);
+            }
+        }
+    }
+}
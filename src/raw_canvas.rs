@@ -1,15 +1,13 @@
+use std::slice;
 use sdl2::video::WindowSurfaceRef;
 use sdl2_sys::{SDL_PixelFormat, SDL_Surface};
-use crate::pixel_color::PixelColor;
+use crate::canvas::{Canvas, PixelFormat};
 
 pub struct RawCanvas {
     width: u32,
     height: u32,
     pixels: *mut u32,
-    red_shift: u8,
-    green_shift: u8,
-    blue_shift: u8,
-    alpha_shift: u8
+    format: PixelFormat
 }
 
 impl RawCanvas {
@@ -21,38 +19,40 @@ impl RawCanvas {
         let green_shift = unsafe { (*format).Gshift };
         let blue_shift = unsafe { (*format).Bshift };
         let alpha_shift = unsafe { (*format).Ashift };
+        let alpha_mask = unsafe { (*format).Amask };
         let width = window_surface.width();
         let height = window_surface.height();
         RawCanvas {
             width,
             height,
             pixels,
-            red_shift,
-            green_shift,
-            blue_shift,
-            alpha_shift
+            format: if alpha_mask != 0 {
+                PixelFormat::new(red_shift, green_shift, blue_shift, alpha_shift)
+            } else {
+                PixelFormat::without_alpha(red_shift, green_shift, blue_shift)
+            }
         }
     }
+}
 
-    pub fn calculate_color_value(&self, color: &PixelColor) -> u32 {
-        (color.red as u32) << (self.red_shift as u32) |
-            (color.blue as u32) << (self.blue_shift as u32) |
-            (color.green as u32) << (self.green_shift as u32) |
-            (color.alpha as u32) << (self.alpha_shift as u32)
+impl Canvas for RawCanvas {
+    fn width(&self) -> u32 {
+        self.width
     }
 
-    pub fn clean_color(&self, color: &PixelColor) {
-        for n in 0..(self.width * self.height) {
-            unsafe {
-                (*self.pixels.offset(n as isize)) = self.calculate_color_value(&color);
-            }
-        }
+    fn height(&self) -> u32 {
+        self.height
     }
 
-    pub fn put_pixel(&self, x: u32, y: u32, color: &PixelColor) {
-        let offset: isize = ((self.height - y - 1) * self.width + x) as isize;
-        unsafe {
-            (*self.pixels.offset(offset)) = self.calculate_color_value(color);
-        }
+    fn format(&self) -> &PixelFormat {
+        &self.format
     }
-}
\ No newline at end of file
+
+    fn pixels(&self) -> &[u32] {
+        unsafe { slice::from_raw_parts(self.pixels, (self.width * self.height) as usize) }
+    }
+
+    fn pixels_mut(&mut self) -> &mut [u32] {
+        unsafe { slice::from_raw_parts_mut(self.pixels, (self.width * self.height) as usize) }
+    }
+}
@@ -0,0 +1,194 @@
+use std::f32::consts::PI;
+use crate::compositing::Compositing;
+use crate::line::Line;
+use crate::line_style::LineStyle;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polygon::Polygon;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Stroke {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f32
+}
+
+// Segment of the stroked polyline with its unit direction
+struct StrokeSegment {
+    from: Point2d,
+    to: Point2d,
+    direction: (f32, f32)
+}
+
+impl Stroke {
+    pub fn new(width: f32) -> Stroke {
+        Stroke { width, cap: LineCap::Butt, join: LineJoin::Miter, miter_limit: 4.0 }
+    }
+
+    pub fn with_cap(&self, cap: LineCap) -> Stroke {
+        Stroke { cap, ..*self }
+    }
+
+    pub fn with_join(&self, join: LineJoin) -> Stroke {
+        Stroke { join, ..*self }
+    }
+
+    pub fn with_miter_limit(&self, miter_limit: f32) -> Stroke {
+        Stroke { miter_limit, ..*self }
+    }
+
+    pub fn stroke_line(&self, line: &Line) -> Vec<Polygon> {
+        self.stroke_styled_polyline(&[line.first, line.last], false, &line.style, line.color, line.compositing)
+    }
+
+    // Every drawn part of the pattern is stroked separately and gets its own caps
+    pub fn stroke_styled_polyline(&self, points: &[Point2d], closed: bool, style: &LineStyle, color: PixelColor,
+                                  compositing: Compositing) -> Vec<Polygon> {
+        if *style == LineStyle::Solid {
+            return self.stroke_polyline(points, closed, color, compositing);
+        }
+        style.split_polyline(points, closed).iter()
+            .flat_map(|run| self.stroke_polyline(run, false, color, compositing))
+            .collect()
+    }
+
+    // Outline is returned as overlapping convex pieces (segment bodies, joins and caps), all oriented counter
+    // clockwise so that filling them together with the non-zero rule covers every pixel once
+    pub fn stroke_polyline(&self, points: &[Point2d], closed: bool, color: PixelColor, compositing: Compositing) -> Vec<Polygon> {
+        let mut pieces: Vec<Vec<Point2d>> = Vec::new();
+        let half_width = self.width / 2.0;
+        if half_width <= 0.0 || !half_width.is_finite() {
+            return Vec::new();
+        }
+        let segments = segments(points, closed);
+        if segments.is_empty() {
+            if let (LineCap::Round, Some(point)) = (self.cap, points.first()) {
+                pieces.push(circle(point, half_width));
+            } else if let (LineCap::Square, Some(point)) = (self.cap, points.first()) {
+                let from = offset(point, (1.0, 0.0), -half_width);
+                let to = offset(point, (1.0, 0.0), half_width);
+                pieces.push(rectangle(&from, &to, (1.0, 0.0), half_width));
+            }
+        }
+        let length = segments.len();
+        for (index, segment) in segments.iter().enumerate() {
+            let is_first = index == 0 && !closed;
+            let is_last = index == length - 1 && !closed;
+            let extend_from = if is_first && self.cap == LineCap::Square { half_width } else { 0.0 };
+            let extend_to = if is_last && self.cap == LineCap::Square { half_width } else { 0.0 };
+            let from = offset(&segment.from, segment.direction, -extend_from);
+            let to = offset(&segment.to, segment.direction, extend_to);
+            pieces.push(rectangle(&from, &to, segment.direction, half_width));
+            if self.cap == LineCap::Round {
+                if is_first {
+                    pieces.push(circle(&segment.from, half_width));
+                }
+                if is_last {
+                    pieces.push(circle(&segment.to, half_width));
+                }
+            }
+            if index + 1 < length || closed {
+                let next = &segments[(index + 1) % length];
+                if let Some(join) = self.join(segment, next, half_width) {
+                    pieces.push(join);
+                }
+            }
+        }
+        pieces.into_iter()
+            .map(|points| Polygon::new(color, counter_clockwise(points)).with_compositing(compositing))
+            .collect()
+    }
+
+    fn join(&self, incoming: &StrokeSegment, outgoing: &StrokeSegment, half_width: f32) -> Option<Vec<Point2d>> {
+        let vertex = incoming.to;
+        let cross = incoming.direction.0 * outgoing.direction.1 - incoming.direction.1 * outgoing.direction.0;
+        let dot = incoming.direction.0 * outgoing.direction.0 + incoming.direction.1 * outgoing.direction.1;
+        if cross.abs() < 1e-6 && dot > 0.0 {
+            return None;
+        }
+        if self.join == LineJoin::Round {
+            return Some(circle(&vertex, half_width));
+        }
+        // Outer side of the turn is on the right for left turns
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let incoming_normal = (-incoming.direction.1 * side, incoming.direction.0 * side);
+        let outgoing_normal = (-outgoing.direction.1 * side, outgoing.direction.0 * side);
+        let incoming_corner = offset(&vertex, incoming_normal, half_width);
+        let outgoing_corner = offset(&vertex, outgoing_normal, half_width);
+        // Ratio of miter length to stroke width equals 1 / cos(turn / 2)
+        let half_turn_cos = ((1.0 + dot) / 2.0).max(0.0).sqrt();
+        if self.join == LineJoin::Miter && half_turn_cos > 0.0 && 1.0 / half_turn_cos <= self.miter_limit {
+            let bisector = normalize((incoming_normal.0 + outgoing_normal.0, incoming_normal.1 + outgoing_normal.1))?;
+            let tip = offset(&vertex, bisector, half_width / half_turn_cos);
+            Some(vec![vertex, incoming_corner, tip, outgoing_corner])
+        } else {
+            Some(vec![vertex, incoming_corner, outgoing_corner])
+        }
+    }
+}
+
+fn normalize(vector: (f32, f32)) -> Option<(f32, f32)> {
+    let length = (vector.0 * vector.0 + vector.1 * vector.1).sqrt();
+    if length > 1e-6 && length.is_finite() {
+        Some((vector.0 / length, vector.1 / length))
+    } else {
+        None
+    }
+}
+
+fn segments(points: &[Point2d], closed: bool) -> Vec<StrokeSegment> {
+    let mut result = Vec::new();
+    let length = points.len();
+    let count = if closed && length > 2 { length } else { length.saturating_sub(1) };
+    for i in 0..count {
+        let from = points[i];
+        let to = points[(i + 1) % length];
+        if let Some(direction) = normalize((to.x - from.x, to.y - from.y)) {
+            result.push(StrokeSegment { from, to, direction });
+        }
+    }
+    result
+}
+
+fn counter_clockwise(points: Vec<Point2d>) -> Vec<Point2d> {
+    let length = points.len();
+    let doubled_area: f32 = (0..length)
+        .map(|i| points[i].x * points[(i + 1) % length].y - points[(i + 1) % length].x * points[i].y)
+        .sum();
+    if doubled_area < 0.0 { points.into_iter().rev().collect() } else { points }
+}
+
+fn offset(point: &Point2d, direction: (f32, f32), distance: f32) -> Point2d {
+    Point2d::new(point.x + direction.0 * distance, point.y + direction.1 * distance)
+}
+
+fn rectangle(from: &Point2d, to: &Point2d, direction: (f32, f32), half_width: f32) -> Vec<Point2d> {
+    let normal = (-direction.1, direction.0);
+    vec![offset(from, normal, half_width), offset(to, normal, half_width),
+         offset(to, normal, -half_width), offset(from, normal, -half_width)]
+}
+
+pub fn circle(center: &Point2d, radius: f32) -> Vec<Point2d> {
+    let count = ((2.0 * PI * radius / 2.0).ceil() as usize).max(8);
+    (0..count)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f32 / count as f32;
+            Point2d::new(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect()
+}
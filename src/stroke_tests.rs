@@ -0,0 +1,41 @@
+use crate::canvas::Canvas;
+use crate::draw::Draw;
+use crate::memory_canvas::MemoryCanvas;
+use crate::pixel_color::PixelColor;
+use crate::point2d::Point2d;
+use crate::polyline::Polyline;
+use crate::stroke::{LineCap, LineJoin, Stroke};
+use crate::golden_tests::{HEIGHT, WIDTH, assert_golden};
+
+#[test]
+fn strokes_caps_and_joins() {
+    let zigzag = |y: f32| vec![Point2d::new(6.0, y), Point2d::new(22.0, y + 10.0), Point2d::new(34.0, y), Point2d::new(56.0, y + 8.0)];
+    assert_golden("strokes_caps_and_joins", vec![
+        Box::new(Polyline::new(PixelColor::red(), zigzag(4.0))
+            .with_stroke(Stroke::new(4.0).with_cap(LineCap::Butt).with_join(LineJoin::Miter))),
+        Box::new(Polyline::new(PixelColor::rgb(0, 255, 0), zigzag(22.0))
+            .with_stroke(Stroke::new(5.0).with_cap(LineCap::Round).with_join(LineJoin::Round))),
+        Box::new(Polyline::new(PixelColor::rgb(0, 0, 255), zigzag(40.0))
+            .with_stroke(Stroke::new(4.0).with_cap(LineCap::Square).with_join(LineJoin::Bevel))),
+        Box::new(Polyline::closed(PixelColor::rgb(255, 255, 0), vec![Point2d::new(44.0, 44.0), Point2d::new(60.0, 46.0),
+                                                                    Point2d::new(50.0, 60.0)])
+            .with_stroke(Stroke::new(2.0).with_miter_limit(1.5)))
+    ]);
+}
+
+// Pieces of the stroke overlap at joins and caps, translucent strokes still blend every pixel once
+#[test]
+fn translucent_strokes_blend_once() {
+    let zigzag = vec![Point2d::new(6.0, 10.0), Point2d::new(22.0, 40.0), Point2d::new(34.0, 12.0), Point2d::new(56.0, 50.0)];
+    for (cap, join) in [(LineCap::Round, LineJoin::Round), (LineCap::Square, LineJoin::Miter), (LineCap::Butt, LineJoin::Bevel)].iter() {
+        let mut canvas = MemoryCanvas::new(WIDTH, HEIGHT);
+        canvas.clean_color(&PixelColor::black());
+        Polyline::new(PixelColor::rgb(255, 255, 255).with_alpha(128), zigzag.clone())
+            .with_stroke(Stroke::new(6.0).with_cap(*cap).with_join(*join))
+            .draw(&mut canvas);
+        let mut levels: Vec<u8> = canvas.pixels().iter().map(|pixel| canvas.format().decode(*pixel).red).collect();
+        levels.sort_unstable();
+        levels.dedup();
+        assert_eq!(levels.len(), 2, "{:?} {:?} gave red levels {:?}", cap, join, levels);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::point2d::Point2d;
+
+// Maps (x, y) to (a * x + c * y + e, b * x + d * y + f)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AffineTransform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32
+}
+
+impl AffineTransform {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> AffineTransform {
+        AffineTransform { a, b, c, d, e, f }
+    }
+
+    pub fn identity() -> AffineTransform {
+        AffineTransform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    pub fn translation(x: f32, y: f32) -> AffineTransform {
+        AffineTransform::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    pub fn scale(x: f32, y: f32) -> AffineTransform {
+        AffineTransform::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    // Counter clockwise around the origin
+    pub fn rotation(angle: f32) -> AffineTransform {
+        let (sin, cos) = angle.sin_cos();
+        AffineTransform::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    pub fn skew(x_angle: f32, y_angle: f32) -> AffineTransform {
+        AffineTransform::new(1.0, y_angle.tan(), x_angle.tan(), 1.0, 0.0, 0.0)
+    }
+
+    // Transform applying self first and other afterwards
+    pub fn then(&self, other: &AffineTransform) -> AffineTransform {
+        AffineTransform::new(other.a * self.a + other.c * self.b,
+                             other.b * self.a + other.d * self.b,
+                             other.a * self.c + other.c * self.d,
+                             other.b * self.c + other.d * self.d,
+                             other.a * self.e + other.c * self.f + other.e,
+                             other.b * self.e + other.d * self.f + other.f)
+    }
+
+    pub fn inverse(&self) -> Option<AffineTransform> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant.abs() <= f32::EPSILON || !determinant.is_finite() {
+            return None;
+        }
+        let a = self.d / determinant;
+        let b = -self.b / determinant;
+        let c = -self.c / determinant;
+        let d = self.a / determinant;
+        Some(AffineTransform::new(a, b, c, d, -(a * self.e + c * self.f), -(b * self.e + d * self.f)))
+    }
+
+    pub fn apply(&self, point: &Point2d) -> Point2d {
+        Point2d::new(self.a * point.x + self.c * point.y + self.e, self.b * point.x + self.d * point.y + self.f)
+    }
+}